@@ -2,15 +2,22 @@ use cosmwasm_std::{
     Deps, DepsMut, Env, MessageInfo, Response, StdResult, Binary
 };
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{Denom, ExecuteMsg, InstantiateMsg, MigrateMsg, PayoutOrder, QueryMsg};
 use crate::state::{
     Config, initialize_state,
     validate_config, validate_amount, CONFIG
 };
 use crate::execute::ExecuteHandler;
+use crate::migrate::MigrateHandler;
 
 pub struct Contract;
 
+impl Default for Contract {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Contract {
     pub fn new() -> Self {
         Contract
@@ -23,19 +30,48 @@ impl Contract {
         _info: MessageInfo,
         msg: InstantiateMsg,
     ) -> Result<Response, ContractError> {
+        // Contributions use the CW20 token when one is configured
+        let denom = match msg.cw20_token {
+            Some(token) => Denom::Cw20(deps.api.addr_validate(&token)?),
+            None => Denom::Native(msg.token_denom),
+        };
+
+        // The admin is tracked as the contract owner so it can be transferred
+        cw_ownable::initialize_owner(deps.storage, deps.api, &msg.admin)?;
+        let admin = deps.api.addr_validate(&msg.admin)?;
+        let fee_recipient = match msg.fee_recipient {
+            Some(fee_recipient) => deps.api.addr_validate(&fee_recipient)?,
+            None => admin.clone(),
+        };
+
+        // The arbitrator leads a panel with any further arbitrators
+        let arbitrators = std::iter::once(&msg.arbitrator)
+            .chain(msg.arbitrators.iter().flatten())
+            .map(|addr| deps.api.addr_validate(addr))
+            .collect::<Result<Vec<_>, _>>()?;
+        let arbitration_quorum = msg.arbitration_quorum.unwrap_or(arbitrators.len() as u64 / 2 + 1);
+
         // Validate configuration
         let config = Config {
-            admin: deps.api.addr_validate(&msg.admin)?,
-            token_denom: msg.token_denom,
+            denom,
             contribution_amount: validate_amount(&msg.contribution_amount)?,
             round_frequency: msg.round_frequency,
             beneficiaries: msg.beneficiaries.iter()
                 .map(|addr| deps.api.addr_validate(addr))
                 .collect::<Result<Vec<_>, _>>()?,
+            payout_order: msg.payout_order.unwrap_or(PayoutOrder::Fixed),
             late_penalty: validate_amount(&msg.late_penalty)?,
             protocol_fees: validate_amount(&msg.protocol_fees)?,
-            arbitrator: deps.api.addr_validate(&msg.arbitrator)?,
+            arbitrators,
+            arbitration_quorum,
             time_guards: msg.time_guards,
+            max_members: msg.max_members,
+            refund_overpayment: msg.refund_overpayment.unwrap_or(false),
+            scale_late_penalty: msg.scale_late_penalty.unwrap_or(false),
+            auction: msg.auction.unwrap_or(false),
+            sealed_bids: msg.sealed_bids.unwrap_or(false),
+            bid_bond: msg.bid_bond.as_deref().map(validate_amount).transpose()?.unwrap_or_default(),
+            fee_recipient,
         };
 
         validate_config(&config)?;
@@ -49,15 +85,25 @@ impl Contract {
         // Create response with events
         let response = Response::new()
             .add_attribute("method", "instantiate")
-            .add_attribute("admin", config.admin.to_string())
-            .add_attribute("token_denom", config.token_denom)
+            .add_attribute("admin", admin.to_string())
+            .add_attribute("token_denom", match &config.denom {
+                Denom::Native(denom) => denom.to_string(),
+                Denom::Cw20(token) => token.to_string(),
+            })
             .add_attribute("contribution_amount", config.contribution_amount.to_string())
             .add_attribute("round_frequency", config.round_frequency.to_string())
             .add_attribute("beneficiaries_count", config.beneficiaries.len().to_string())
             .add_attribute("late_penalty", config.late_penalty.to_string())
             .add_attribute("protocol_fees", config.protocol_fees.to_string())
-            .add_attribute("arbitrator", config.arbitrator.to_string())
-            .add_attribute("time_guards", config.time_guards.to_string());
+            .add_attribute("arbitrators", config.arbitrators.len().to_string())
+            .add_attribute("arbitration_quorum", config.arbitration_quorum.to_string())
+            .add_attribute("time_guards", config.time_guards.to_string())
+            .add_attribute("refund_overpayment", config.refund_overpayment.to_string())
+            .add_attribute("scale_late_penalty", config.scale_late_penalty.to_string())
+            .add_attribute("auction", config.auction.to_string())
+            .add_attribute("sealed_bids", config.sealed_bids.to_string())
+            .add_attribute("bid_bond", config.bid_bond.to_string())
+            .add_attribute("fee_recipient", config.fee_recipient.to_string());
 
         Ok(response)
     }
@@ -69,7 +115,7 @@ impl Contract {
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
-        let result = match msg {
+        match msg {
             ExecuteMsg::RegisterMember { address } => {
                 ExecuteHandler::register_member(deps, env, info, address)
             }
@@ -79,18 +125,54 @@ impl Contract {
             ExecuteMsg::ReplaceMember { old_address, new_address } => {
                 ExecuteHandler::replace_member(deps, env, info, old_address, new_address)
             }
+            ExecuteMsg::ExcludeMember { address } => {
+                ExecuteHandler::exclude_member(deps, env, info, address)
+            }
+            ExecuteMsg::RequestToJoin {} => {
+                ExecuteHandler::request_to_join(deps, env, info)
+            }
+            ExecuteMsg::ApproveJoin { address } => {
+                ExecuteHandler::approve_join(deps, env, info, address)
+            }
+            ExecuteMsg::RejectJoin { address } => {
+                ExecuteHandler::reject_join(deps, env, info, address)
+            }
+            ExecuteMsg::LeaveWaitlist {} => {
+                ExecuteHandler::leave_waitlist(deps, env, info)
+            }
+            ExecuteMsg::CreateInvitation { code_hash, max_uses, expires_at } => {
+                ExecuteHandler::create_invitation(deps, env, info, code_hash, max_uses, expires_at)
+            }
+            ExecuteMsg::RevokeInvitation { code_hash } => {
+                ExecuteHandler::revoke_invitation(deps, env, info, code_hash)
+            }
+            ExecuteMsg::AcceptInvitation { code } => {
+                ExecuteHandler::accept_invitation(deps, env, info, code)
+            }
             ExecuteMsg::StartTontine {} => {
                 ExecuteHandler::start_tontine(deps, env, info)
             }
             ExecuteMsg::PauseTontine {} => {
                 ExecuteHandler::pause_tontine(deps, env, info)
             }
+            ExecuteMsg::UpdatePayoutOrder { payout_order } => {
+                ExecuteHandler::update_payout_order(deps, env, info, payout_order)
+            }
+            ExecuteMsg::CommitSeed { commitment } => {
+                ExecuteHandler::commit_seed(deps, env, info, commitment)
+            }
+            ExecuteMsg::RevealSeed { secret } => {
+                ExecuteHandler::reveal_seed(deps, env, info, secret)
+            }
             ExecuteMsg::ResumeTontine {} => {
                 ExecuteHandler::resume_tontine(deps, env, info)
             }
             ExecuteMsg::CloseEarly { reason } => {
                 ExecuteHandler::close_early(deps, env, info, reason)
             }
+            ExecuteMsg::ClaimRefund {} => {
+                ExecuteHandler::claim_refund(deps, env, info)
+            }
             ExecuteMsg::DepositContribution {} => {
                 ExecuteHandler::deposit_contribution(deps, env, info)
             }
@@ -100,6 +182,15 @@ impl Contract {
             ExecuteMsg::AdvancePayment { beneficiary, discount } => {
                 ExecuteHandler::advance_payment(deps, env, info, beneficiary, discount)
             }
+            ExecuteMsg::PlaceBid { discount } => {
+                ExecuteHandler::place_bid(deps, env, info, discount)
+            }
+            ExecuteMsg::CommitBid { hash } => {
+                ExecuteHandler::commit_bid(deps, env, info, hash)
+            }
+            ExecuteMsg::RevealBid { discount, salt } => {
+                ExecuteHandler::reveal_bid(deps, env, info, discount, salt)
+            }
             ExecuteMsg::DeclareLate { member } => {
                 ExecuteHandler::declare_late(deps, env, info, member)
             }
@@ -112,27 +203,56 @@ impl Contract {
             ExecuteMsg::WithdrawFees {} => {
                 ExecuteHandler::withdraw_fees(deps, env, info)
             }
-            ExecuteMsg::CollectProtocolFees {} => {
-                ExecuteHandler::collect_protocol_fees(deps, env, info)
+            ExecuteMsg::WithdrawPenalties {} => {
+                ExecuteHandler::withdraw_penalties(deps, env, info)
+            }
+            ExecuteMsg::UpdateFeeRecipient { fee_recipient } => {
+                ExecuteHandler::update_fee_recipient(deps, env, info, fee_recipient)
+            }
+            ExecuteMsg::OpenDispute { reason, round } => {
+                ExecuteHandler::open_dispute(deps, env, info, reason, round)
+            }
+            ExecuteMsg::ReviewDispute { member, round } => {
+                ExecuteHandler::review_dispute(deps, env, info, member, round)
+            }
+            ExecuteMsg::ResolveDispute { member, round, resolution } => {
+                ExecuteHandler::resolve_dispute(deps, env, info, member, round, resolution)
             }
-            ExecuteMsg::ResolveDispute { member, resolution } => {
-                ExecuteHandler::resolve_dispute(deps, env, info, member, resolution)
+            ExecuteMsg::AppealDispute { round } => {
+                ExecuteHandler::appeal_dispute(deps, env, info, round)
             }
-            ExecuteMsg::ArbitrateDispute { member, decision } => {
-                ExecuteHandler::arbitrate_dispute(deps, env, info, member, decision)
+            ExecuteMsg::ArbitrateDispute { member, round, decision, release } => {
+                ExecuteHandler::arbitrate_dispute(deps, env, info, member, round, decision, release)
+            }
+            ExecuteMsg::ReplaceArbitrator { arbitrator, replacement } => {
+                ExecuteHandler::replace_arbitrator(deps, env, info, arbitrator, replacement)
+            }
+            ExecuteMsg::GrantRole { address, role } => {
+                ExecuteHandler::grant_role(deps, env, info, address, role)
+            }
+            ExecuteMsg::RevokeRole { address, role } => {
+                ExecuteHandler::revoke_role(deps, env, info, address, role)
             }
             ExecuteMsg::FinalizeTontine {} => {
                 ExecuteHandler::finalize_tontine(deps, env, info)
             }
-            ExecuteMsg::Migrate { new_code_id } => {
-                ExecuteHandler::migrate(deps, env, info, new_code_id)
+            ExecuteMsg::RecomputeTotals {} => {
+                ExecuteHandler::recompute_totals(deps, env, info)
             }
-        };
-
-        result
+            ExecuteMsg::UpdateOwnership(action) => {
+                ExecuteHandler::update_ownership(deps, env, info, action)
+            }
+            ExecuteMsg::Receive(wrapper) => {
+                ExecuteHandler::receive_cw20(deps, env, info, wrapper)
+            }
+        }
     }
 
     pub fn query(&self, deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
         crate::query::QueryHandler::handle_query(deps, msg)
     }
+
+    pub fn migrate(&self, deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        MigrateHandler::migrate(deps, env, msg)
+    }
 }
@@ -1,23 +1,125 @@
 use cosmwasm_std::{
-    DepsMut, Env, MessageInfo, Response, Uint128, BankMsg
+    from_json, to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, HexBinary, MessageInfo,
+    Order, Response, StdResult, Storage, Timestamp, Uint128, WasmMsg
 };
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use sha2::{Digest, Sha256};
 use crate::error::ContractError;
-use crate::msg::{MemberStatus, RoundState};
+use crate::msg::{Denom, DisputeStatus, EscrowRelease, MemberStatus, PayoutOrder, ReceiveMsg, Role, RoundState};
 use crate::state::{
-    Member, Round, Distribution,
-    get_config, get_tontine_state, get_member, get_current_round,
-    get_accumulated_fees, validate_member_address, validate_amount, members, ROUNDS, DISTRIBUTIONS,
-    ACCUMULATED_FEES, TONTINE_STATE
+    ArbitrationVote, Bid, BidCommitment, Config, Invitation, JoinRequest, Member, SeedCommitment, WaitlistEntry, Round, Distribution, Dispute, Penalty, TontineState,
+    get_config, get_tontine_state, get_escrow_state, get_member, get_round, get_current_round, get_dispute,
+    get_accumulated_fees, get_round_deposits, get_totals, compute_totals, update_totals, has_deposited, active_member_count, active_members, active_members_by_registration, waitlist_position, validate_member_address, validate_amount, members, ROUNDS, DEPOSITS, DISTRIBUTIONS,
+    ACCUMULATED_FEES, ARBITRATION_VOTES, BIDS, BID_COMMITMENTS, DISCOUNT_CREDITS, ARBITRATOR_REPLACEMENTS, COLLECTED_PENALTIES, CONFIG, DISPUTES, ESCROW_REFUNDED, ESCROW_STATE, INVITATIONS, JOIN_REQUESTS, LATE_PENALTIES, MEMBER_CONTRIBUTIONS, RANDOM_SEED, REFUNDS, PENALTIES, PENALTY_COUNT, ROLES,
+    ROTATION, SEED_COMMITMENTS, SEED_COMMIT_DEADLINE, TONTINE_STATE, TOTALS, WAITLIST, WAITLIST_COUNT, WITHDRAWN_FEES, WITHDRAWN_PENALTIES
 };
 
+const SECONDS_PER_DAY: u64 = 86400;
 
+pub struct ExecuteHandler;
 
+impl ExecuteHandler {
+    // The admin is the contract owner managed by cw-ownable
+    fn assert_admin(storage: &dyn Storage, sender: &Addr, msg: &str) -> Result<(), ContractError> {
+        cw_ownable::assert_owner(storage, sender).map_err(|_| ContractError::Unauthorized { 
+            msg: msg.to_string() 
+        })
+    }
 
+    fn is_admin(storage: &dyn Storage, sender: &Addr) -> bool {
+        cw_ownable::assert_owner(storage, sender).is_ok()
+    }
 
+    // The admin holds every role
+    fn has_role(storage: &dyn Storage, sender: &Addr, role: Role) -> StdResult<bool> {
+        if Self::is_admin(storage, sender) {
+            return Ok(true);
+        }
+        Ok(ROLES.may_load(storage, sender)?.unwrap_or_default().contains(&role))
+    }
 
-pub struct ExecuteHandler;
+    fn assert_role(storage: &dyn Storage, sender: &Addr, role: Role, msg: &str) -> Result<(), ContractError> {
+        if !Self::has_role(storage, sender, role)? {
+            return Err(ContractError::Unauthorized { msg: msg.to_string() });
+        }
+        Ok(())
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        // Only admin can grant roles
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can grant roles")?;
+
+        let addr = deps.api.addr_validate(&address)?;
+        let mut roles = ROLES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        if roles.contains(&role) {
+            return Err(ContractError::InvalidConfiguration { 
+                msg: format!("{} already has the {:?} role", address, role) 
+            });
+        }
+        roles.push(role.clone());
+        ROLES.save(deps.storage, &addr, &roles)?;
+
+        let response = Response::new()
+            .add_attribute("method", "grant_role")
+            .add_attribute("address", address)
+            .add_attribute("role", format!("{:?}", role));
+
+        Ok(response)
+    }
+
+    pub fn revoke_role(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        // Only admin can revoke roles
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can revoke roles")?;
+
+        let addr = deps.api.addr_validate(&address)?;
+        let mut roles = ROLES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        if !roles.contains(&role) {
+            return Err(ContractError::InvalidConfiguration { 
+                msg: format!("{} does not have the {:?} role", address, role) 
+            });
+        }
+        roles.retain(|granted| *granted != role);
+        if roles.is_empty() {
+            ROLES.remove(deps.storage, &addr);
+        } else {
+            ROLES.save(deps.storage, &addr, &roles)?;
+        }
+
+        let response = Response::new()
+            .add_attribute("method", "revoke_role")
+            .add_attribute("address", address)
+            .add_attribute("role", format!("{:?}", role));
+
+        Ok(response)
+    }
+
+    pub fn update_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: cw_ownable::Action,
+    ) -> Result<Response, ContractError> {
+        let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+
+        let response = Response::new()
+            .add_attribute("method", "update_ownership")
+            .add_attributes(ownership.into_attributes());
+
+        Ok(response)
+    }
 
-impl ExecuteHandler {
     // Member Management Functions
     
     pub fn register_member(
@@ -26,62 +128,307 @@ impl ExecuteHandler {
         info: MessageInfo,
         address: String,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
-        
-        // Only admin can register members
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can register members".to_string() 
-            });
-        }
+        // Only admin or registrars can register members
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can register members")?;
+
+        // Members registered after the start would never be in the payout rotation
+        Self::assert_not_started(deps.storage)?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
 
         // Validate address
         validate_member_address(&address)?;
         let member_addr = deps.api.addr_validate(&address)?;
 
+        let waitlisted = Self::add_member(deps.storage, &env, &member_addr, Uint128::zero())?;
+
+        // Create response
+        let response = Response::new()
+            .add_attribute("method", "register_member")
+            .add_attribute("member", address);
+
+        Ok(Self::add_admission_attributes(response, &env, waitlisted))
+    }
+
+    // Saves a new active member, holding an optional bond. When the tontine is full
+    // the address joins the waitlist instead and its position is returned
+    fn add_member(
+        storage: &mut dyn Storage,
+        env: &Env,
+        address: &Addr,
+        bond: Uint128,
+    ) -> Result<Option<u64>, ContractError> {
         // Check if member already exists
-        if members().has(deps.storage, member_addr.as_str()) {
-            return Err(ContractError::MemberAlreadyExists { address });
+        if members().has(storage, address.as_str()) {
+            return Err(ContractError::MemberAlreadyExists { address: address.to_string() });
+        }
+        if waitlist_position(storage, address)?.is_some() {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: format!("{} is already on the waitlist", address) 
+            });
+        }
+
+        if Self::is_full(storage)? {
+            let id = WAITLIST_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+            WAITLIST_COUNT.save(storage, &id)?;
+            WAITLIST.save(storage, id, &WaitlistEntry {
+                address: address.clone(),
+                bond,
+                timestamp: env.block.time,
+            })?;
+            return Ok(waitlist_position(storage, address)?);
         }
 
-        // Create new member
+        Self::save_new_member(storage, env, address, bond)?;
+
+        Ok(None)
+    }
+
+    fn save_new_member(
+        storage: &mut dyn Storage,
+        env: &Env,
+        address: &Addr,
+        bond: Uint128,
+    ) -> Result<(), ContractError> {
         let member = Member {
-            address: member_addr.clone(),
+            address: address.clone(),
             status: MemberStatus::Active,
-            balance: Uint128::zero(),
+            balance: bond,
             penalties: Uint128::zero(),
             last_contribution: None,
             is_late: false,
             registration_time: env.block.time,
         };
+        members().save(storage, address.as_str(), &member)?;
+        update_totals(storage, |totals| totals.member_count += 1)?;
 
-        // Save member
-        members().save(deps.storage, member_addr.as_str(), &member)?;
+        Ok(())
+    }
+
+    fn is_full(storage: &dyn Storage) -> Result<bool, ContractError> {
+        let config = get_config(storage)?;
+        match config.max_members {
+            Some(max_members) => Ok(active_member_count(storage)? >= max_members),
+            None => Ok(false),
+        }
+    }
+
+    fn add_admission_attributes(response: Response, env: &Env, waitlisted: Option<u64>) -> Response {
+        match waitlisted {
+            Some(position) => response.add_attribute("waitlist_position", position.to_string()),
+            None => response.add_attribute("registration_time", env.block.time.to_string()),
+        }
+    }
+
+    // Before the start, a freed slot goes to the first address on the waitlist
+    fn promote_from_waitlist(
+        storage: &mut dyn Storage,
+        env: &Env,
+        response: Response,
+    ) -> Result<Response, ContractError> {
+        let state = get_tontine_state(storage)?;
+        if state.is_active || state.is_finished || state.current_round > 0 || Self::is_full(storage)? {
+            return Ok(response);
+        }
+
+        let next = WAITLIST
+            .range(storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        let Some((id, entry)) = next else {
+            return Ok(response);
+        };
+        WAITLIST.remove(storage, id);
+        Self::save_new_member(storage, env, &entry.address, entry.bond)?;
+
+        Ok(response.add_attribute("promoted", entry.address.to_string()))
+    }
+
+    // A waitlisted address gives up its place and gets its bond back
+    pub fn leave_waitlist(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let waitlisted = WAITLIST
+            .range(deps.storage, None, None, Order::Ascending)
+            .find(|item| matches!(item, Ok((_, entry)) if entry.address == info.sender))
+            .transpose()?;
+        let Some((id, entry)) = waitlisted else {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: format!("{} is not on the waitlist", info.sender) 
+            });
+        };
+        WAITLIST.remove(deps.storage, id);
+
+        let mut response = Response::new()
+            .add_attribute("method", "leave_waitlist")
+            .add_attribute("member", info.sender.to_string());
+
+        // Return the bond
+        if !entry.bond.is_zero() {
+            let config = get_config(deps.storage)?;
+            response = response
+                .add_message(Self::transfer_msg(&config.denom, &entry.address, entry.bond)?)
+                .add_attribute("refunded", entry.bond.to_string());
+        }
+
+        Ok(response)
+    }
+
+    pub fn exclude_member(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        // Only admin or registrars can exclude members
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can exclude members")?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
+
+        let mut member = get_member(deps.storage, &deps.api.addr_validate(&address)?)?;
+        if member.status != MemberStatus::Active {
+            return Err(ContractError::InvalidMemberState { 
+                state: format!("{:?}", member.status) 
+            });
+        }
+
+        // Excluded members keep their record but can no longer take part
+        member.status = MemberStatus::Excluded;
+        members().save(deps.storage, member.address.as_str(), &member)?;
 
-        // Create response
         let response = Response::new()
-            .add_attribute("method", "register_member")
+            .add_attribute("method", "exclude_member")
             .add_attribute("member", address)
-            .add_attribute("registration_time", env.block.time.to_string());
+            .add_attribute("exclusion_time", env.block.time.to_string());
+
+        Self::promote_from_waitlist(deps.storage, &env, response)
+    }
+
+    pub fn create_invitation(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        code_hash: HexBinary,
+        max_uses: u32,
+        expires_at: Option<Timestamp>,
+    ) -> Result<Response, ContractError> {
+        // Only admin or registrars can invite members
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can create invitations")?;
+
+        if code_hash.len() != 32 {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Invitation code hash must be a SHA-256 digest".to_string() 
+            });
+        }
+        if max_uses == 0 {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Invitation must allow at least one use".to_string() 
+            });
+        }
+        if expires_at.is_some_and(|expires_at| expires_at <= env.block.time) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Invitation expiry must be in the future".to_string() 
+            });
+        }
+        if INVITATIONS.has(deps.storage, code_hash.as_slice()) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: format!("Invitation {} already exists", code_hash) 
+            });
+        }
+
+        let invitation = Invitation {
+            code_hash: code_hash.clone(),
+            max_uses,
+            uses: 0,
+            expires_at,
+        };
+        INVITATIONS.save(deps.storage, code_hash.as_slice(), &invitation)?;
+
+        let response = Response::new()
+            .add_attribute("method", "create_invitation")
+            .add_attribute("code_hash", code_hash.to_hex())
+            .add_attribute("max_uses", max_uses.to_string());
+
+        Ok(response)
+    }
+
+    pub fn revoke_invitation(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        code_hash: HexBinary,
+    ) -> Result<Response, ContractError> {
+        // Only admin or registrars can revoke invitations
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can revoke invitations")?;
+
+        if !INVITATIONS.has(deps.storage, code_hash.as_slice()) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: format!("No invitation {}", code_hash) 
+            });
+        }
+        INVITATIONS.remove(deps.storage, code_hash.as_slice());
+
+        let response = Response::new()
+            .add_attribute("method", "revoke_invitation")
+            .add_attribute("code_hash", code_hash.to_hex());
 
         Ok(response)
     }
 
+    // Registers the caller when the code hashes to a valid invitation
+    pub fn accept_invitation(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        code: String,
+    ) -> Result<Response, ContractError> {
+        let code_hash = Sha256::digest(code.as_bytes());
+        let mut invitation = INVITATIONS.may_load(deps.storage, code_hash.as_slice())?.ok_or_else(|| {
+            ContractError::InvalidMemberManagement { 
+                msg: "Invalid invitation code".to_string() 
+            }
+        })?;
+
+        if invitation.expires_at.is_some_and(|expires_at| env.block.time >= expires_at) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Invitation has expired".to_string() 
+            });
+        }
+
+        // Invitations accepted after the start would leave the member out of the rotation
+        Self::assert_not_started(deps.storage)?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
+
+        validate_member_address(info.sender.as_str())?;
+        let waitlisted = Self::add_member(deps.storage, &env, &info.sender, Uint128::zero())?;
+
+        // Used up invitations are removed
+        invitation.uses += 1;
+        if invitation.uses >= invitation.max_uses {
+            INVITATIONS.remove(deps.storage, code_hash.as_slice());
+        } else {
+            INVITATIONS.save(deps.storage, code_hash.as_slice(), &invitation)?;
+        }
+
+        let response = Response::new()
+            .add_attribute("method", "accept_invitation")
+            .add_attribute("member", info.sender.to_string())
+            .add_attribute("code_hash", invitation.code_hash.to_hex());
+
+        Ok(Self::add_admission_attributes(response, &env, waitlisted))
+    }
+
     pub fn remove_member(
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
         address: String,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
         let state = get_tontine_state(deps.storage)?;
         
-        // Only admin can remove members
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can remove members".to_string() 
-            });
-        }
+        // Only admin or registrars can remove members
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can remove members")?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
 
         // Cannot remove members during active round
         if state.is_active && state.current_round > 0 {
@@ -100,16 +447,169 @@ impl ExecuteHandler {
         }
 
         // Remove member
-        members().remove(deps.storage, address.as_str());
+        members().remove(deps.storage, address.as_str())?;
+        update_totals(deps.storage, |totals| totals.member_count = totals.member_count.saturating_sub(1))?;
 
-        let response = Response::new()
+        let mut response = Response::new()
             .add_attribute("method", "remove_member")
             .add_attribute("member", address)
             .add_attribute("removal_time", env.block.time.to_string());
 
+        // Return the joining bond
+        if !member.balance.is_zero() {
+            let config = get_config(deps.storage)?;
+            response = response
+                .add_message(Self::transfer_msg(&config.denom, &member.address, member.balance)?)
+                .add_attribute("bond_returned", member.balance.to_string());
+        }
+
+        Self::promote_from_waitlist(deps.storage, &env, response)
+    }
+
+    pub fn request_to_join(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+
+        // The deposit is optional
+        let deposit = if info.funds.is_empty() {
+            Uint128::zero()
+        } else {
+            Self::native_payment(&config, &info)?
+        };
+        Self::record_join_request(deps, env, info.sender, deposit)
+    }
+
+    // Stores a pending join request, with a deposit paid in native or CW20 tokens
+    fn record_join_request(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        deposit: Uint128,
+    ) -> Result<Response, ContractError> {
+        Self::assert_not_started(deps.storage)?;
+
+        validate_member_address(sender.as_str())?;
+        if members().has(deps.storage, sender.as_str()) {
+            return Err(ContractError::MemberAlreadyExists { address: sender.to_string() });
+        }
+        if JOIN_REQUESTS.has(deps.storage, &sender) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: format!("{} already requested to join", sender) 
+            });
+        }
+
+        let request = JoinRequest {
+            address: sender.clone(),
+            deposit,
+            timestamp: env.block.time,
+        };
+        JOIN_REQUESTS.save(deps.storage, &sender, &request)?;
+
+        let response = Response::new()
+            .add_attribute("method", "request_to_join")
+            .add_attribute("member", sender.to_string())
+            .add_attribute("deposit", deposit.to_string());
+
+        Ok(response)
+    }
+
+    pub fn approve_join(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        // Only admin or registrars can approve join requests
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can approve join requests")?;
+        Self::assert_not_started(deps.storage)?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
+
+        let request = Self::take_join_request(deps.storage, &deps.api.addr_validate(&address)?)?;
+
+        // The deposit is held as the member's bond
+        let waitlisted = Self::add_member(deps.storage, &env, &request.address, request.deposit)?;
+
+        let response = Response::new()
+            .add_attribute("method", "approve_join")
+            .add_attribute("member", address)
+            .add_attribute("bond", request.deposit.to_string());
+
+        Ok(Self::add_admission_attributes(response, &env, waitlisted))
+    }
+
+    pub fn reject_join(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        // Only admin or registrars can reject join requests
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can reject join requests")?;
+        Self::assert_not_started(deps.storage)?;
+
+        let request = Self::take_join_request(deps.storage, &deps.api.addr_validate(&address)?)?;
+
+        let mut response = Response::new()
+            .add_attribute("method", "reject_join")
+            .add_attribute("member", address);
+
+        // Return the deposit
+        if !request.deposit.is_zero() {
+            let config = get_config(deps.storage)?;
+            response = response
+                .add_message(Self::transfer_msg(&config.denom, &request.address, request.deposit)?)
+                .add_attribute("refunded", request.deposit.to_string());
+        }
+
         Ok(response)
     }
 
+    fn take_join_request(storage: &mut dyn Storage, address: &Addr) -> Result<JoinRequest, ContractError> {
+        let request = JOIN_REQUESTS.may_load(storage, address)?.ok_or_else(|| {
+            ContractError::InvalidMemberManagement { 
+                msg: format!("No join request from {}", address) 
+            }
+        })?;
+        JOIN_REQUESTS.remove(storage, address);
+        Ok(request)
+    }
+
+    // Members only join before the first round, so every active member is in the rotation
+    fn assert_not_started(storage: &dyn Storage) -> Result<(), ContractError> {
+        let state = get_tontine_state(storage)?;
+        if state.is_finished {
+            return Err(ContractError::TontineAlreadyFinished);
+        }
+        if state.is_active || state.current_round > 0 {
+            return Err(ContractError::TontineAlreadyStarted);
+        }
+        Ok(())
+    }
+
+    // The active members and the payout order the random seed is drawn over are
+    // frozen from the first commitment until the draw, so the admin cannot steer it
+    fn assert_seed_draw_not_open(storage: &dyn Storage) -> Result<(), ContractError> {
+        if !SEED_COMMITMENTS.is_empty(storage) && RANDOM_SEED.may_load(storage)?.is_none() {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Membership and payout order are frozen once seeds are committed".to_string() 
+            });
+        }
+        Ok(())
+    }
+
+    fn assert_active_member(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+        let member = get_member(storage, sender)?;
+        if member.status != MemberStatus::Active {
+            return Err(ContractError::InvalidMemberState { 
+                state: format!("{:?}", member.status) 
+            });
+        }
+        Ok(())
+    }
+
     pub fn replace_member(
         deps: DepsMut,
         env: Env,
@@ -117,15 +617,11 @@ impl ExecuteHandler {
         old_address: String,
         new_address: String,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
         let state = get_tontine_state(deps.storage)?;
         
-        // Only admin can replace members
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can replace members".to_string() 
-            });
-        }
+        // Only admin or registrars can replace members
+        Self::assert_role(deps.storage, &info.sender, Role::Registrar, "Only admin or registrars can replace members")?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
 
         // Cannot replace members during active round
         if state.is_active && state.current_round > 0 {
@@ -157,32 +653,41 @@ impl ExecuteHandler {
             return Err(ContractError::MemberHasPenalties);
         }
 
-        // Create new member with old member's balance
+        // Create new member, the replacement did not post the old member's bond
         let new_member = Member {
             address: new_addr.clone(),
             status: MemberStatus::Active,
-            balance: old_member.balance,
+            balance: Uint128::zero(),
             penalties: Uint128::zero(),
             last_contribution: old_member.last_contribution,
             is_late: false,
             registration_time: env.block.time,
         };
 
-        // Update old member status
+        // Update old member status and release its bond
+        let bond = old_member.balance;
         old_member.status = MemberStatus::Replaced;
+        old_member.balance = Uint128::zero();
 
         // Save both members
         members().save(deps.storage, old_address.as_str(), &old_member)?;
         members().save(deps.storage, new_address.as_str(), &new_member)?;
+        update_totals(deps.storage, |totals| totals.member_count += 1)?;
 
-
-
-        let response = Response::new()
+        let mut response = Response::new()
             .add_attribute("method", "replace_member")
             .add_attribute("old_member", old_address)
             .add_attribute("new_member", new_address)
             .add_attribute("replacement_time", env.block.time.to_string());
 
+        // Return the bond to the old member
+        if !bond.is_zero() {
+            let config = get_config(deps.storage)?;
+            response = response
+                .add_message(Self::transfer_msg(&config.denom, &old_addr, bond)?)
+                .add_attribute("refunded", bond.to_string());
+        }
+
         Ok(response)
     }
 
@@ -197,11 +702,7 @@ impl ExecuteHandler {
         let mut state = get_tontine_state(deps.storage)?;
         
         // Only admin can start tontine
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can start tontine".to_string() 
-            });
-        }
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can start tontine")?;
 
         // Check if tontine is already started
         if state.is_active {
@@ -213,34 +714,22 @@ impl ExecuteHandler {
             return Err(ContractError::TontineAlreadyFinished);
         }
 
-        // Get member count - since Map doesn't have range(), we need to check if any members exist
-        // We'll check if at least one member is registered by looking at the beneficiaries
-        let mut member_count = 0;
-        for beneficiary in &config.beneficiaries {
-            if members().has(deps.storage, beneficiary.as_str()) {
-                member_count += 1;
-            }
-        }
-        
-        if member_count == 0 {
-            return Err(ContractError::InvalidMemberManagement { 
-                msg: "No members registered".to_string() 
-            });
-        }
-        
-        // Ensure first beneficiary is registered
-        let first_beneficiary = &config.beneficiaries[0];
-        if !members().has(deps.storage, first_beneficiary.as_str()) {
-            return Err(ContractError::InvalidMemberManagement { 
-                msg: "First beneficiary is not registered as a member".to_string() 
-            });
+        // Members who let the reveal window pass without revealing forfeit their place
+        let forfeited = Self::forfeit_unrevealed_seeds(deps.storage, &env, &config)?;
+
+        // Every active member receives exactly one payout
+        let (rotation, seed) = Self::build_rotation(deps.as_ref(), &config)?;
+        ROTATION.save(deps.storage, &rotation)?;
+        if let Some(seed) = &seed {
+            RANDOM_SEED.save(deps.storage, seed)?;
         }
+        let first_beneficiary = &rotation[0];
 
         // Start tontine
         state.is_active = true;
         state.start_time = Some(env.block.time);
         state.current_round = 1;
-        state.total_rounds = member_count as u64;
+        state.total_rounds = rotation.len() as u64;
 
         // Create first round
         let first_round = Round {
@@ -249,7 +738,7 @@ impl ExecuteHandler {
             balance: Uint128::zero(),
             beneficiary: first_beneficiary.clone(),
             deadline: env.block.time.plus_seconds(config.round_frequency),
-            deposits: vec![],
+            deposit_count: 0,
             is_distributed: false,
             distribution_time: None,
         };
@@ -257,6 +746,7 @@ impl ExecuteHandler {
         // Save state and round
         TONTINE_STATE.save(deps.storage, &state)?;
         ROUNDS.save(deps.storage, 1, &first_round)?;
+        update_totals(deps.storage, |totals| totals.active_rounds += 1)?;
 
         let response = Response::new()
             .add_attribute("method", "start_tontine")
@@ -264,6 +754,12 @@ impl ExecuteHandler {
             .add_attribute("current_round", "1")
             .add_attribute("total_rounds", state.total_rounds.to_string())
             .add_attribute("first_beneficiary", first_beneficiary.to_string());
+        let response = match seed {
+            Some(seed) => response
+                .add_attribute("random_seed", seed.to_hex())
+                .add_attribute("forfeited_seeds", forfeited.to_string()),
+            None => response,
+        };
 
         Ok(response)
     }
@@ -273,15 +769,10 @@ impl ExecuteHandler {
         env: Env,
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
         let mut state = get_tontine_state(deps.storage)?;
         
         // Only admin can pause tontine
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can pause tontine".to_string() 
-            });
-        }
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can pause tontine")?;
 
         // Check if tontine is active
         if !state.is_active {
@@ -304,15 +795,10 @@ impl ExecuteHandler {
         env: Env,
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
         let mut state = get_tontine_state(deps.storage)?;
         
         // Only admin can resume tontine
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can resume tontine".to_string() 
-            });
-        }
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can resume tontine")?;
 
         // Check if tontine is paused
         if !state.is_paused {
@@ -338,15 +824,10 @@ impl ExecuteHandler {
         info: MessageInfo,
         reason: String,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
         let mut state = get_tontine_state(deps.storage)?;
         
         // Only admin can close tontine early
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can close tontine early".to_string() 
-            });
-        }
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can close tontine early")?;
 
         // Check if tontine is active
         if !state.is_active {
@@ -358,15 +839,216 @@ impl ExecuteHandler {
             return Err(ContractError::TontineAlreadyFinished);
         }
 
+        // A disputed round must be arbitrated first, or its deposits would be refunded twice
+        let escrow = get_escrow_state(deps.storage)?;
+        if escrow.is_locked {
+            return Err(ContractError::InvalidEscrowManagement { 
+                msg: "Cannot close while dispute funds are locked in escrow".to_string() 
+            });
+        }
+
         // Close tontine early
         state.is_active = false;
         state.is_finished = true;
         TONTINE_STATE.save(deps.storage, &state)?;
 
+        // Undistributed funds are returned to members through ClaimRefund
+        let (refunded, retained) = Self::record_refunds(deps.storage, &env)?;
+
         let response = Response::new()
             .add_attribute("method", "close_early")
             .add_attribute("reason", reason)
-            .add_attribute("close_time", env.block.time.to_string());
+            .add_attribute("close_time", env.block.time.to_string())
+            .add_attribute("refunded", refunded.to_string())
+            .add_attribute("retained", retained.to_string());
+
+        Ok(response)
+    }
+
+    // Splits the undistributed balance pro rata over each member's net position: contributions
+    // net of protocol fees minus payouts received. Rounds already settled through an escrow
+    // release are left out, and unpaid penalties the refund covers are settled from it
+    fn record_refunds(storage: &mut dyn Storage, env: &Env) -> Result<(Uint128, Uint128), ContractError> {
+        let config = get_config(storage)?;
+        let mut pool = Uint128::zero();
+        let mut distributed: Vec<u64> = vec![];
+        for item in ROUNDS.range(storage, None, None, Order::Ascending) {
+            let (round_number, round) = item?;
+            if !round.is_distributed {
+                pool += round.balance;
+            } else if !ESCROW_REFUNDED.has(storage, round_number) {
+                distributed.push(round_number);
+            }
+        }
+
+        // Distributed rounds paid out their deposits minus the protocol fee
+        let mut contributed: Vec<(Addr, Uint128)> = vec![];
+        for item in DEPOSITS.range(storage, None, None, Order::Ascending) {
+            let ((round_number, _), deposit) = item?;
+            if ESCROW_REFUNDED.has(storage, round_number) {
+                continue;
+            }
+            let amount = if distributed.contains(&round_number) {
+                deposit.amount.saturating_sub(config.protocol_fees)
+            } else {
+                deposit.amount
+            };
+            match contributed.iter_mut().find(|(member, _)| *member == deposit.member) {
+                Some((_, total)) => *total += amount,
+                None => contributed.push((deposit.member, amount)),
+            }
+        }
+
+        let distributions = DISTRIBUTIONS
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, distribution)| distribution))
+            .filter(|item| item.as_ref().map_or(true, |distribution| distributed.contains(&distribution.round)))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut positions: Vec<(Addr, Uint128)> = vec![];
+        for (member_addr, amount) in contributed {
+            let received: Uint128 = distributions.iter()
+                .filter(|distribution| distribution.beneficiary == member_addr)
+                .map(|distribution| distribution.amount)
+                .sum::<Uint128>()
+                + DISCOUNT_CREDITS.may_load(storage, &member_addr)?.unwrap_or_default();
+
+            let net = amount.saturating_sub(received);
+            if !net.is_zero() {
+                positions.push((member_addr, net));
+            }
+        }
+
+        // Members are made whole when the pool allows it, otherwise scaled down
+        let total_net: Uint128 = positions.iter().map(|(_, net)| *net).sum();
+        let mut refunded = Uint128::zero();
+        for (member_addr, net) in positions {
+            let entitled = if pool >= total_net {
+                net
+            } else {
+                pool.multiply_ratio(net, total_net)
+            };
+            let withheld = Self::settle_penalties_from_refund(storage, env, &member_addr, entitled)?;
+            let refund = entitled - withheld;
+            if !refund.is_zero() {
+                REFUNDS.save(storage, &member_addr, &refund)?;
+                Self::deduct_contribution(storage, &member_addr, refund)?;
+                refunded += refund;
+            }
+        }
+
+        // Whatever remains was withheld for penalties or is rounding dust, and goes to the organiser
+        let retained = pool - refunded;
+        if !retained.is_zero() {
+            COLLECTED_PENALTIES.update(storage, |collected| -> StdResult<_> { Ok(collected + retained) })?;
+        }
+
+        Ok((refunded, retained))
+    }
+
+    // Takes refunded money off the member's running contribution total
+    fn deduct_contribution(storage: &mut dyn Storage, member_addr: &Addr, amount: Uint128) -> StdResult<()> {
+        MEMBER_CONTRIBUTIONS.update(storage, member_addr, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().saturating_sub(amount))
+        })?;
+        Ok(())
+    }
+
+    // Marks the member's unpaid penalties as paid, oldest first, as long as the refund
+    // covers them in full, and returns the amount withheld for them
+    fn settle_penalties_from_refund(
+        storage: &mut dyn Storage,
+        env: &Env,
+        member_addr: &Addr,
+        refund: Uint128,
+    ) -> Result<Uint128, ContractError> {
+        let Some(mut member) = members().may_load(storage, member_addr.as_str())? else {
+            return Ok(Uint128::zero());
+        };
+        if member.penalties.is_zero() {
+            return Ok(Uint128::zero());
+        }
+
+        let unpaid: Vec<(u64, Penalty)> = PENALTIES
+            .prefix(member_addr)
+            .range(storage, None, None, Order::Ascending)
+            .filter(|item| item.as_ref().map_or(true, |(_, penalty)| !penalty.is_paid))
+            .collect::<StdResult<_>>()?;
+
+        let mut withheld = Uint128::zero();
+        for (penalty_id, mut penalty) in unpaid {
+            if withheld + penalty.amount > refund {
+                break;
+            }
+            withheld += penalty.amount;
+            penalty.is_paid = true;
+            penalty.payment_time = Some(env.block.time);
+            PENALTIES.save(storage, (member_addr, penalty_id), &penalty)?;
+        }
+
+        if !withheld.is_zero() {
+            update_totals(storage, |totals| totals.penalties_paid += withheld)?;
+            member.penalties = member.penalties.saturating_sub(withheld);
+            if member.penalties.is_zero() {
+                member.is_late = false;
+            }
+            members().save(storage, member_addr.as_str(), &member)?;
+        }
+
+        Ok(withheld)
+    }
+
+    pub fn claim_refund(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+        let state = get_tontine_state(deps.storage)?;
+
+        let mut refund = REFUNDS.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+        REFUNDS.remove(deps.storage, &info.sender);
+
+        // Joining bonds, including those of waitlisted addresses, are released once the tontine is over
+        if state.is_finished {
+            if let Some(mut member) = members().may_load(deps.storage, info.sender.as_str())? {
+                refund += member.balance;
+                member.balance = Uint128::zero();
+                members().save(deps.storage, info.sender.as_str(), &member)?;
+            }
+            let waitlisted = WAITLIST
+                .range(deps.storage, None, None, Order::Ascending)
+                .find(|item| matches!(item, Ok((_, entry)) if entry.address == info.sender))
+                .transpose()?;
+            if let Some((id, entry)) = waitlisted {
+                refund += entry.bond;
+                WAITLIST.remove(deps.storage, id);
+            }
+
+            // Sealed bid bonds of rounds that were never distributed
+            let bonds = BID_COMMITMENTS
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter(|item| matches!(item, Ok(((_, member), commitment)) if *member == info.sender && !commitment.revealed))
+                .collect::<StdResult<Vec<_>>>()?;
+            for ((round_number, _), commitment) in bonds {
+                if !get_round(deps.storage, round_number)?.is_distributed {
+                    refund += commitment.bond;
+                    BID_COMMITMENTS.remove(deps.storage, (round_number, &info.sender));
+                }
+            }
+        }
+
+        if refund.is_zero() {
+            return Err(ContractError::InvalidWithdrawal { 
+                msg: format!("No refund available for {}", info.sender) 
+            });
+        }
+
+        let response = Response::new()
+            .add_message(Self::transfer_msg(&config.denom, &info.sender, refund)?)
+            .add_attribute("method", "claim_refund")
+            .add_attribute("member", info.sender.to_string())
+            .add_attribute("amount", refund.to_string());
 
         Ok(response)
     }
@@ -377,6 +1059,18 @@ impl ExecuteHandler {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+        let paid = Self::native_payment(&config, &info)?;
+        Self::contribute(deps, env, info.sender, paid)
+    }
+
+    // Records a contribution paid with either native or CW20 tokens
+    fn contribute(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        paid: Uint128,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
         let state = get_tontine_state(deps.storage)?;
@@ -400,7 +1094,7 @@ impl ExecuteHandler {
         }
 
         // Check if member exists and is active
-        let member = get_member(deps.storage, &info.sender)?;
+        let member = get_member(deps.storage, &sender)?;
         if member.status != MemberStatus::Active {
             return Err(ContractError::InvalidMemberState { 
                 state: format!("{:?}", member.status) 
@@ -408,7 +1102,7 @@ impl ExecuteHandler {
         }
 
         // Check if member already contributed to this round
-        if round.deposits.iter().any(|d| d.member == info.sender) {
+        if has_deposited(deps.storage, round.round_number, &sender) {
             return Err(ContractError::MemberAlreadyContributed);
         }
 
@@ -417,19 +1111,33 @@ impl ExecuteHandler {
             return Err(ContractError::MemberHasPenalties);
         }
 
+        // Check attached funds cover exactly one contribution
+        if paid < config.contribution_amount {
+            return Err(ContractError::InvalidDeposit { 
+                msg: format!("Contribution requires {}, received {}", config.contribution_amount, paid) 
+            });
+        }
+        let excess = paid - config.contribution_amount;
+        if !excess.is_zero() && !config.refund_overpayment {
+            return Err(ContractError::InvalidDeposit { 
+                msg: format!("Contribution requires {}, received {}", config.contribution_amount, paid) 
+            });
+        }
+
         // Check if deadline has passed
         let is_late = env.block.time > round.deadline;
 
         // Create deposit
         let deposit = crate::state::Deposit {
-            member: info.sender.clone(),
+            member: sender.clone(),
             amount: config.contribution_amount,
             timestamp: env.block.time,
             is_late,
         };
 
         // Add deposit to round
-        round.deposits.push(deposit);
+        DEPOSITS.save(deps.storage, (round.round_number, &sender), &deposit)?;
+        round.deposit_count += 1;
         round.balance += config.contribution_amount;
 
         // Update member's last contribution
@@ -439,30 +1147,150 @@ impl ExecuteHandler {
 
         // Save round and member
         ROUNDS.save(deps.storage, state.current_round, &round)?;
-        members().save(deps.storage, info.sender.as_str(), &updated_member)?;
+        members().save(deps.storage, sender.as_str(), &updated_member)?;
+        update_totals(deps.storage, |totals| totals.contributions += config.contribution_amount)?;
+        MEMBER_CONTRIBUTIONS.update(deps.storage, &sender, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() + config.contribution_amount)
+        })?;
 
-        let response = Response::new()
+        // Keep the escrow in line with the balance of a locked round
+        let mut escrow = get_escrow_state(deps.storage)?;
+        if escrow.is_locked && escrow.locked_round == Some(state.current_round) {
+            escrow.locked_amount = round.balance;
+            ESCROW_STATE.save(deps.storage, &escrow)?;
+        }
+
+        let mut response = Response::new()
             .add_attribute("method", "deposit_contribution")
-            .add_attribute("member", info.sender.to_string())
+            .add_attribute("member", sender.to_string())
             .add_attribute("amount", config.contribution_amount.to_string())
             .add_attribute("round", state.current_round.to_string())
             .add_attribute("is_late", is_late.to_string());
 
+        // Deposits made after the time_guards grace window accrue the late penalty
+        let grace_end = round.deadline.plus_seconds(config.time_guards);
+        if env.block.time > grace_end {
+            let penalty_amount = Self::late_penalty_for(&config, round.deadline, env.block.time);
+            let penalty_id = Self::assess_late_penalty(
+                deps.storage,
+                &env,
+                &sender,
+                state.current_round,
+                penalty_amount,
+            )?;
+            if let Some(penalty_id) = penalty_id {
+                response = response
+                    .add_attribute("penalty_id", penalty_id.to_string())
+                    .add_attribute("penalty", penalty_amount.to_string());
+            }
+        }
+
+        // Refund any overpayment in the same transaction
+        if !excess.is_zero() {
+            response = response
+                .add_message(Self::transfer_msg(&config.denom, &sender, excess)?)
+                .add_attribute("refund", excess.to_string());
+        }
+
         Ok(response)
     }
 
+    pub fn receive_cw20(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        wrapper: Cw20ReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+
+        // Only the configured CW20 token contract can call the receive hook
+        match &config.denom {
+            Denom::Cw20(token) if *token == info.sender => {}
+            _ => {
+                return Err(ContractError::InvalidCw20Receive { 
+                    msg: format!("Unsupported token contract: {}", info.sender) 
+                });
+            }
+        }
+
+        let sender = deps.api.addr_validate(&wrapper.sender)?;
+        match from_json(&wrapper.msg)? {
+            ReceiveMsg::DepositContribution {} => {
+                Self::contribute(deps, env, sender, wrapper.amount)
+            }
+            ReceiveMsg::PayPenalty { member } => {
+                Self::settle_penalty(deps, env, sender, member, wrapper.amount)
+            }
+            ReceiveMsg::RequestToJoin {} => {
+                Self::record_join_request(deps, env, sender, wrapper.amount)
+            }
+            ReceiveMsg::CommitBid { hash } => {
+                Self::record_bid_commitment(deps, env, sender, hash, wrapper.amount)
+            }
+        }
+    }
+
+    // Amount of the configured native token attached to the message
+    fn native_payment(config: &Config, info: &MessageInfo) -> Result<Uint128, ContractError> {
+        let denom = match &config.denom {
+            Denom::Native(denom) => denom,
+            Denom::Cw20(token) => {
+                return Err(ContractError::InvalidNativeFunds { 
+                    msg: format!("Payments must be sent through the CW20 contract {}", token) 
+                });
+            }
+        };
+
+        match info.funds.as_slice() {
+            [] => Err(ContractError::InvalidNativeFunds { 
+                msg: format!("No {} sent", denom) 
+            }),
+            [coin] if coin.denom == *denom => Ok(coin.amount),
+            [coin] => Err(ContractError::InvalidNativeFunds { 
+                msg: format!("Expected {}, received {}", denom, coin.denom) 
+            }),
+            _ => Err(ContractError::InvalidNativeFunds { 
+                msg: format!("Only {} can be sent", denom) 
+            }),
+        }
+    }
+
+    // Message sending tokens of the configured denomination out of the contract
+    fn transfer_msg(denom: &Denom, recipient: &Addr, amount: Uint128) -> Result<CosmosMsg, ContractError> {
+        let msg = match denom {
+            Denom::Native(denom) => BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount,
+                }],
+            }
+            .into(),
+            Denom::Cw20(token) => WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        };
+        Ok(msg)
+    }
+
     pub fn distribute_to_beneficiary(
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
-        let state = get_tontine_state(deps.storage)?;
+        let mut state = get_tontine_state(deps.storage)?;
         
-        // Only admin or arbitrator can distribute
-        if info.sender != config.admin && info.sender != config.arbitrator {
+        // Only admin, operators or arbitrators can distribute
+        if !Self::has_role(deps.storage, &info.sender, Role::Operator)? && !config.arbitrators.contains(&info.sender) {
             return Err(ContractError::Unauthorized { 
-                msg: "Only admin or arbitrator can distribute".to_string() 
+                msg: "Only admin, operators or arbitrators can distribute".to_string() 
             });
         }
 
@@ -491,50 +1319,474 @@ impl ExecuteHandler {
             });
         }
 
+        // Round funds held in escrow by a dispute cannot be distributed
+        let escrow = get_escrow_state(deps.storage)?;
+        if escrow.is_locked && escrow.locked_round == Some(round.round_number) {
+            return Err(ContractError::InvalidEscrowManagement { 
+                msg: format!("Round {} funds are locked by a dispute", round.round_number) 
+            });
+        }
+
+        // Members who have not deposited yet may still do so without penalty until the
+        // time_guards grace window is over
+        let missing: Vec<Member> = active_members(deps.storage)?
+            .into_iter()
+            .filter(|member| !has_deposited(deps.storage, round.round_number, &member.address))
+            .collect();
+        if !missing.is_empty() && env.block.time <= round.deadline.plus_seconds(config.time_guards) {
+            return Err(ContractError::RoundDeadlineNotReached);
+        }
+
         // Calculate distribution amount (total balance minus fees)
-        let total_fees = config.protocol_fees * Uint128::from(round.deposits.len() as u32);
-        let distribution_amount = round.balance - total_fees;
+        let total_fees = config.protocol_fees * Uint128::from(round.deposit_count);
+        let pot = round.balance - total_fees;
+
+        // In auction mode the highest bidder takes the pot and shares the discount
+        let (distribution_amount, credits) = Self::settle_round(deps.storage, &config, &mut round, pot)?;
+        let forfeited_bonds = Self::forfeit_unrevealed_bonds(deps.storage, round.round_number)?;
+
+        // Active members who missed the round accrue the late penalty
+        let penalty_amount = Self::late_penalty_for(&config, round.deadline, env.block.time);
+        for mut member in missing.iter().cloned() {
+            member.is_late = true;
+            members().save(deps.storage, member.address.as_str(), &member)?;
+            Self::assess_late_penalty(
+                deps.storage,
+                &env,
+                &member.address,
+                state.current_round,
+                penalty_amount,
+            )?;
+        }
 
-        // Update round state
-        round.state = RoundState::Distributed;
-        round.is_distributed = true;
-        round.distribution_time = Some(env.block.time);
+        // Create transfer message for distribution
+        let transfer_msg = Self::transfer_msg(&config.denom, &round.beneficiary, distribution_amount)?;
+        let credit_msgs = credits.iter()
+            .map(|(member, amount)| Self::transfer_msg(&config.denom, member, *amount))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        // Save round
-        ROUNDS.save(deps.storage, state.current_round, &round)?;
+        let distributed_round = state.current_round;
 
-        // Create distribution record
+        // Record the payout, then open the next round or finish the tontine after the last one
         let distribution = Distribution {
-            round: state.current_round,
+            round: distributed_round,
             beneficiary: round.beneficiary.clone(),
             amount: distribution_amount,
+            discount: pot - distribution_amount,
+            advanced_from: None,
             timestamp: env.block.time,
         };
-
-        // Save distribution
-        DISTRIBUTIONS.save(deps.storage, state.current_round, &distribution)?;
-
-        // Update accumulated fees
-        let mut accumulated_fees = get_accumulated_fees(deps.storage)?;
-        accumulated_fees += total_fees;
-        ACCUMULATED_FEES.save(deps.storage, &accumulated_fees)?;
-
-        // Create bank message for distribution
-        let bank_msg = BankMsg::Send {
-            to_address: round.beneficiary.to_string(),
-            amount: vec![cosmwasm_std::Coin {
-                denom: config.token_denom.clone(),
-                amount: distribution_amount,
-            }],
-        };
+        let next_round = Self::record_payout(deps.storage, &env, &config, &mut state, &mut round, distribution, total_fees)?;
 
         let response = Response::new()
-            .add_message(bank_msg)
+            .add_message(transfer_msg)
+            .add_messages(credit_msgs)
             .add_attribute("method", "distribute_to_beneficiary")
-            .add_attribute("round", state.current_round.to_string())
+            .add_attribute("round", distributed_round.to_string())
             .add_attribute("beneficiary", round.beneficiary.to_string())
             .add_attribute("amount", distribution_amount.to_string())
-            .add_attribute("fees", total_fees.to_string());
+            .add_attribute("discount", (pot - distribution_amount).to_string())
+            .add_attribute("fees", total_fees.to_string())
+            .add_attribute("forfeited_bonds", forfeited_bonds.to_string())
+            .add_attribute("missing_deposits", missing.len().to_string());
+
+        Ok(Self::add_next_round_attributes(response, next_round))
+    }
+
+    // Awards the pot to the eligible member bidding the largest discount, earliest bid first
+    // on ties, and shares the discount among the other contributors. Returns the winner's
+    // payout and the credits.
+    fn settle_round(
+        storage: &mut dyn Storage,
+        config: &Config,
+        round: &mut Round,
+        pot: Uint128,
+    ) -> Result<(Uint128, Vec<(Addr, Uint128)>), ContractError> {
+        if !config.auction {
+            return Ok((pot, vec![]));
+        }
+
+        let mut rotation = ROTATION.load(storage)?;
+        let slot = (round.round_number - 1) as usize;
+        let mut winner: Option<(Addr, Bid)> = None;
+        for item in BIDS.prefix(round.round_number).range(storage, None, None, Order::Ascending) {
+            let (bidder, bid) = item?;
+            // Members already paid, or who left, cannot win
+            if !rotation[slot..].contains(&bidder) {
+                continue;
+            }
+            let is_better = winner.as_ref().is_none_or(|(_, best)| {
+                bid.discount > best.discount || (bid.discount == best.discount && bid.timestamp < best.timestamp)
+            });
+            if is_better {
+                winner = Some((bidder, bid));
+            }
+        }
+        let Some((winner, bid)) = winner else {
+            return Ok((pot, vec![]));
+        };
+
+        // The winner takes this round's slot in the rotation
+        if let Some(position) = rotation.iter().position(|addr| *addr == winner) {
+            rotation.swap(slot, position);
+            ROTATION.save(storage, &rotation)?;
+        }
+        round.beneficiary = winner;
+
+        let credits = Self::share_discount(storage, round, bid.discount.min(pot))?;
+        let shared: Uint128 = credits.iter().map(|(_, amount)| *amount).sum();
+
+        Ok((pot - shared, credits))
+    }
+
+    // Splits a discount evenly among the round's contributors other than its beneficiary
+    // and records their credits. The remainder of the split stays with the beneficiary.
+    fn share_discount(
+        storage: &mut dyn Storage,
+        round: &Round,
+        discount: Uint128,
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        let others: Vec<Addr> = get_round_deposits(storage, round.round_number)?
+            .into_iter()
+            .map(|deposit| deposit.member)
+            .filter(|member| *member != round.beneficiary)
+            .collect();
+        if others.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let share = discount / Uint128::from(others.len() as u64);
+        if share.is_zero() {
+            return Ok(vec![]);
+        }
+        for member in &others {
+            DISCOUNT_CREDITS.update(storage, member, |credits| -> StdResult<_> {
+                Ok(credits.unwrap_or_default() + share)
+            })?;
+        }
+
+        Ok(others.into_iter().map(|member| (member, share)).collect())
+    }
+
+    // Marks the round distributed and records its payout and fees, then opens the
+    // next round or finishes the tontine after the last one
+    fn record_payout(
+        storage: &mut dyn Storage,
+        env: &Env,
+        config: &Config,
+        state: &mut TontineState,
+        round: &mut Round,
+        distribution: Distribution,
+        total_fees: Uint128,
+    ) -> Result<Option<Round>, ContractError> {
+        round.state = RoundState::Distributed;
+        round.is_distributed = true;
+        round.distribution_time = Some(env.block.time);
+        ROUNDS.save(storage, round.round_number, round)?;
+        DISTRIBUTIONS.save(storage, round.round_number, &distribution)?;
+        update_totals(storage, |totals| {
+            totals.distributions += distribution.amount;
+            totals.active_rounds = totals.active_rounds.saturating_sub(1);
+            totals.completed_rounds += 1;
+        })?;
+
+        let accumulated_fees = get_accumulated_fees(storage)? + total_fees;
+        ACCUMULATED_FEES.save(storage, &accumulated_fees)?;
+
+        Self::advance_round(storage, env, config, state)
+    }
+
+    // Opens the round after the current one, or finishes the tontine after the last round
+    fn advance_round(
+        storage: &mut dyn Storage,
+        env: &Env,
+        config: &Config,
+        state: &mut TontineState,
+    ) -> Result<Option<Round>, ContractError> {
+        state.last_round_time = Some(env.block.time);
+        let next_round = if state.current_round < state.total_rounds {
+            let next_round_number = state.current_round + 1;
+            let next_beneficiary = Self::beneficiary_for_round(storage, next_round_number)?;
+
+            let next_round = Round {
+                round_number: next_round_number,
+                state: RoundState::Active,
+                balance: Uint128::zero(),
+                beneficiary: next_beneficiary,
+                deadline: env.block.time.plus_seconds(config.round_frequency),
+                deposit_count: 0,
+                is_distributed: false,
+                distribution_time: None,
+            };
+            ROUNDS.save(storage, next_round_number, &next_round)?;
+            update_totals(storage, |totals| totals.active_rounds += 1)?;
+
+            state.current_round = next_round_number;
+            Some(next_round)
+        } else {
+            state.is_active = false;
+            state.is_finished = true;
+            None
+        };
+        TONTINE_STATE.save(storage, state)?;
+
+        Ok(next_round)
+    }
+
+    fn add_next_round_attributes(response: Response, next_round: Option<Round>) -> Response {
+        match next_round {
+            Some(next_round) => response
+                .add_attribute("next_round", next_round.round_number.to_string())
+                .add_attribute("next_beneficiary", next_round.beneficiary.to_string())
+                .add_attribute("next_deadline", next_round.deadline.to_string()),
+            None => response.add_attribute("is_finished", "true"),
+        }
+    }
+
+    // Beneficiary of a given round, following the rotation built at start
+    fn beneficiary_for_round(storage: &dyn Storage, round_number: u64) -> Result<Addr, ContractError> {
+        let rotation = ROTATION.may_load(storage)?.unwrap_or_default();
+        round_number
+            .checked_sub(1)
+            .and_then(|index| rotation.get(index as usize))
+            .cloned()
+            .ok_or(ContractError::InvalidBeneficiaryIndex { index: round_number })
+    }
+
+    // Payout order over the active members, following the configured PayoutOrder,
+    // along with the seed it was drawn from when random
+    fn build_rotation(deps: Deps, config: &Config) -> Result<(Vec<Addr>, Option<HexBinary>), ContractError> {
+        let active = active_members(deps.storage)?;
+
+        if active.is_empty() {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "No members registered".to_string() 
+            });
+        }
+
+        let mut seed = None;
+        let rotation = match &config.payout_order {
+            PayoutOrder::Fixed => config.beneficiaries.clone(),
+            // Members registered in the same block are ordered by address
+            PayoutOrder::Registration => active_members_by_registration(deps.storage)?
+                .into_iter()
+                .map(|member| member.address)
+                .collect(),
+            PayoutOrder::Custom { order } => order.iter()
+                .map(|addr| deps.api.addr_validate(addr))
+                .collect::<StdResult<Vec<_>>>()?,
+            PayoutOrder::Random => {
+                let drawn = Self::draw_seed(deps.storage, &active)?;
+                let rotation = Self::shuffle(&drawn, active.iter().map(|member| member.address.clone()).collect());
+                seed = Some(drawn);
+                rotation
+            }
+        };
+
+        let is_complete = rotation.len() == active.len()
+            && active.iter().all(|member| rotation.iter().filter(|addr| **addr == member.address).count() == 1);
+        if !is_complete {
+            return Err(ContractError::InvalidBeneficiariesList);
+        }
+
+        Ok((rotation, seed))
+    }
+
+    // Once the reveal window is over, active members without a revealed secret are
+    // excluded so the draw can go ahead without them
+    fn forfeit_unrevealed_seeds(storage: &mut dyn Storage, env: &Env, config: &Config) -> Result<u64, ContractError> {
+        if config.payout_order != PayoutOrder::Random {
+            return Ok(0);
+        }
+        let Some(commit_deadline) = SEED_COMMIT_DEADLINE.may_load(storage)? else {
+            return Ok(0);
+        };
+        if env.block.time <= commit_deadline.plus_seconds(config.time_guards) {
+            return Ok(0);
+        }
+
+        let mut forfeited = 0;
+        for mut member in active_members(storage)? {
+            let revealed = SEED_COMMITMENTS.may_load(storage, &member.address)?
+                .is_some_and(|commitment| commitment.secret.is_some());
+            if !revealed {
+                member.status = MemberStatus::Excluded;
+                members().save(storage, member.address.as_str(), &member)?;
+                forfeited += 1;
+            }
+        }
+        Ok(forfeited)
+    }
+
+    // Seed combining the secrets revealed by every active member, in address order
+    fn draw_seed(storage: &dyn Storage, active: &[Member]) -> Result<HexBinary, ContractError> {
+        let mut hasher = Sha256::new();
+        for member in active {
+            let secret = SEED_COMMITMENTS.may_load(storage, &member.address)?
+                .and_then(|commitment| commitment.secret)
+                .ok_or_else(|| ContractError::InvalidMemberManagement { 
+                    msg: format!("Member {} has not revealed a seed", member.address) 
+                })?;
+            hasher.update(member.address.as_bytes());
+            hasher.update(Sha256::digest(secret.as_bytes()));
+        }
+        Ok(HexBinary::from(hasher.finalize().as_slice()))
+    }
+
+    // Fisher-Yates shuffle driven by hashes of the seed, so anyone can replay it
+    fn shuffle(seed: &HexBinary, mut addresses: Vec<Addr>) -> Vec<Addr> {
+        for i in (1..addresses.len()).rev() {
+            let digest = Sha256::new()
+                .chain_update(seed.as_slice())
+                .chain_update((i as u64).to_be_bytes())
+                .finalize();
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&digest[..8]);
+            let j = (u64::from_be_bytes(bytes) % (i as u64 + 1)) as usize;
+            addresses.swap(i, j);
+        }
+        addresses
+    }
+
+    pub fn commit_seed(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        commitment: HexBinary,
+    ) -> Result<Response, ContractError> {
+        Self::assert_not_started(deps.storage)?;
+        Self::assert_active_member(deps.storage, &info.sender)?;
+
+        // Commitments freeze membership, so they are only taken for a random draw
+        let config = get_config(deps.storage)?;
+        if config.payout_order != PayoutOrder::Random {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Payout order is not random".to_string() 
+            });
+        }
+
+        if commitment.len() != 32 {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Commitment must be a SHA-256 hash".to_string() 
+            });
+        }
+
+        // Commitments are closed once the first secret is revealed
+        let revealing = SEED_COMMITMENTS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, commitment)| commitment.secret.is_some()))
+            .collect::<StdResult<Vec<_>>>()?
+            .contains(&true);
+        if revealing {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Seed reveal has already begun".to_string() 
+            });
+        }
+
+        // The first commitment opens the commit window for time_guards
+        let deadline = match SEED_COMMIT_DEADLINE.may_load(deps.storage)? {
+            Some(deadline) if env.block.time > deadline => {
+                return Err(ContractError::InvalidMemberManagement { 
+                    msg: "Seed commitments are closed".to_string() 
+                });
+            }
+            Some(deadline) => deadline,
+            None => {
+                let deadline = env.block.time.plus_seconds(config.time_guards);
+                SEED_COMMIT_DEADLINE.save(deps.storage, &deadline)?;
+                deadline
+            }
+        };
+
+        SEED_COMMITMENTS.save(deps.storage, &info.sender, &SeedCommitment { commitment, secret: None })?;
+
+        let response = Response::new()
+            .add_attribute("method", "commit_seed")
+            .add_attribute("member", info.sender)
+            .add_attribute("commit_deadline", deadline.to_string());
+
+        Ok(response)
+    }
+
+    pub fn reveal_seed(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        secret: String,
+    ) -> Result<Response, ContractError> {
+        Self::assert_not_started(deps.storage)?;
+        Self::assert_active_member(deps.storage, &info.sender)?;
+
+        let config = get_config(deps.storage)?;
+        let mut commitment = SEED_COMMITMENTS.may_load(deps.storage, &info.sender)?.ok_or_else(|| {
+            ContractError::InvalidMemberManagement { 
+                msg: format!("Member {} has not committed a seed", info.sender) 
+            }
+        })?;
+        let commit_deadline = SEED_COMMIT_DEADLINE.load(deps.storage)?;
+
+        // Secrets are only revealed once every active member is bound by a commitment,
+        // or the commit window is over
+        if env.block.time <= commit_deadline {
+            for member in active_members(deps.storage)? {
+                if !SEED_COMMITMENTS.has(deps.storage, &member.address) {
+                    return Err(ContractError::InvalidMemberManagement { 
+                        msg: format!("Member {} has not committed a seed", member.address) 
+                    });
+                }
+            }
+        }
+        if env.block.time > commit_deadline.plus_seconds(config.time_guards) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Seed reveals are closed".to_string() 
+            });
+        }
+
+        if commitment.secret.is_some() {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Seed already revealed".to_string() 
+            });
+        }
+        if Sha256::digest(secret.as_bytes()).as_slice() != commitment.commitment.as_slice() {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Secret does not match the commitment".to_string() 
+            });
+        }
+
+        commitment.secret = Some(secret);
+        SEED_COMMITMENTS.save(deps.storage, &info.sender, &commitment)?;
+
+        let response = Response::new()
+            .add_attribute("method", "reveal_seed")
+            .add_attribute("member", info.sender);
+
+        Ok(response)
+    }
+
+    pub fn update_payout_order(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        payout_order: PayoutOrder,
+    ) -> Result<Response, ContractError> {
+        // Only admin can change the payout order
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can update the payout order")?;
+        Self::assert_not_started(deps.storage)?;
+        Self::assert_seed_draw_not_open(deps.storage)?;
+
+        if let PayoutOrder::Custom { order } = &payout_order {
+            for addr in order {
+                deps.api.addr_validate(addr)?;
+            }
+        }
+
+        let mut config = get_config(deps.storage)?;
+        config.payout_order = payout_order;
+        CONFIG.save(deps.storage, &config)?;
+
+        let response = Response::new()
+            .add_attribute("method", "update_payout_order")
+            .add_attribute("payout_order", format!("{:?}", config.payout_order));
 
         Ok(response)
     }
@@ -542,31 +1794,32 @@ impl ExecuteHandler {
     // Additional execute functions would be implemented here...
     // For brevity, I'm showing the key ones above
 
+    // Pays a member scheduled for a later round the current round's pot, less a discount
+    // shared among the members who financed it. The member takes the current slot in the
+    // rotation and the current beneficiary takes theirs.
     pub fn advance_payment(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         beneficiary: String,
         discount: String,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
-        
+        let mut state = get_tontine_state(deps.storage)?;
+
         // Only admin can make advance payments
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can make advance payments".to_string() 
-            });
-        }
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can make advance payments")?;
 
         // Validate beneficiary address
         let beneficiary_addr = deps.api.addr_validate(&beneficiary)?;
         
-        // Check if beneficiary is in the list
-        if !config.beneficiaries.contains(&beneficiary_addr) {
+        // Check if beneficiary is in the rotation
+        let mut rotation = ROTATION.may_load(deps.storage)?.unwrap_or_default();
+        let Some(scheduled) = rotation.iter().position(|addr| *addr == beneficiary_addr) else {
             return Err(ContractError::InvalidMemberManagement { 
-                msg: "Beneficiary not in beneficiaries list".to_string() 
+                msg: "Beneficiary not in the payout rotation".to_string() 
             });
-        }
+        };
 
         // Parse discount (percentage as string)
         let discount_percent = discount.parse::<u64>().map_err(|_| ContractError::InvalidAmount { 
@@ -579,26 +1832,335 @@ impl ExecuteHandler {
             });
         }
 
+        if config.auction {
+            return Err(ContractError::CannotAdvancePayment { 
+                msg: "Pots are allocated by auction".to_string() 
+            });
+        }
+
+        if !state.is_active || state.is_paused {
+            return Err(ContractError::TontineNotStarted);
+        }
+
+        let mut round = get_current_round(deps.storage)?;
+        if round.state != RoundState::Active || round.is_distributed {
+            return Err(ContractError::RoundNotActive);
+        }
+
+        let escrow = get_escrow_state(deps.storage)?;
+        if escrow.is_locked && escrow.locked_round == Some(round.round_number) {
+            return Err(ContractError::CannotAdvancePayment { 
+                msg: format!("Round {} funds are locked by a dispute", round.round_number) 
+            });
+        }
+
+        // Only payouts still to come can be advanced
+        let slot = (round.round_number - 1) as usize;
+        if scheduled <= slot {
+            return Err(ContractError::CannotAdvancePayment { 
+                msg: format!("{} is not scheduled for a later round", beneficiary_addr) 
+            });
+        }
+
+        // The advance is financed by the round's contributions, so every member must have paid
+        let missing = active_members(deps.storage)?
+            .iter()
+            .filter(|member| !has_deposited(deps.storage, round.round_number, &member.address))
+            .count();
+        if missing > 0 {
+            return Err(ContractError::CannotAdvancePayment { 
+                msg: format!("{} contributions missing for round {}", missing, round.round_number) 
+            });
+        }
+
+        rotation.swap(slot, scheduled);
+        ROTATION.save(deps.storage, &rotation)?;
+        round.beneficiary = beneficiary_addr.clone();
+
+        let total_fees = config.protocol_fees * Uint128::from(round.deposit_count);
+        let pot = round.balance - total_fees;
+        let credits = Self::share_discount(deps.storage, &round, pot.multiply_ratio(discount_percent, 100u64))?;
+        let shared: Uint128 = credits.iter().map(|(_, amount)| *amount).sum();
+        let amount = pot - shared;
+
+        let transfer_msg = Self::transfer_msg(&config.denom, &beneficiary_addr, amount)?;
+        let credit_msgs = credits.iter()
+            .map(|(member, amount)| Self::transfer_msg(&config.denom, member, *amount))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let advanced_round = round.round_number;
+        let scheduled_round = scheduled as u64 + 1;
+        let distribution = Distribution {
+            round: advanced_round,
+            beneficiary: beneficiary_addr.clone(),
+            amount,
+            discount: shared,
+            advanced_from: Some(scheduled_round),
+            timestamp: env.block.time,
+        };
+        let next_round = Self::record_payout(deps.storage, &env, &config, &mut state, &mut round, distribution, total_fees)?;
+
         let response = Response::new()
+            .add_message(transfer_msg)
+            .add_messages(credit_msgs)
             .add_attribute("method", "advance_payment")
+            .add_attribute("round", advanced_round.to_string())
             .add_attribute("beneficiary", beneficiary)
+            .add_attribute("scheduled_round", scheduled_round.to_string())
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("discount", shared.to_string())
+            .add_attribute("fees", total_fees.to_string());
+
+        Ok(Self::add_next_round_attributes(response, next_round))
+    }
+
+    pub fn place_bid(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        discount: String,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+        let state = get_tontine_state(deps.storage)?;
+
+        if config.sealed_bids {
+            return Err(ContractError::InvalidConfiguration { 
+                msg: "Bids must be sealed with CommitBid".to_string() 
+            });
+        }
+
+        // Bids are accepted until the round deadline
+        let round = Self::assert_can_bid(deps.storage, &config, &state, &info.sender)?;
+        if env.block.time > round.deadline {
+            return Err(ContractError::RoundDeadlineExceeded);
+        }
+        let discount = Self::validate_discount(deps.storage, &config, &discount)?;
+
+        // A new bid replaces the member's previous one
+        BIDS.save(deps.storage, (round.round_number, &info.sender), &Bid {
+            discount,
+            timestamp: env.block.time,
+        })?;
+
+        let response = Response::new()
+            .add_attribute("method", "place_bid")
+            .add_attribute("round", round.round_number.to_string())
+            .add_attribute("member", info.sender)
             .add_attribute("discount", discount);
 
-        Ok(response)
+        Ok(response)
+    }
+
+    // Current round, provided the sender may bid for its pot
+    fn assert_can_bid(
+        storage: &dyn Storage,
+        config: &Config,
+        state: &TontineState,
+        sender: &Addr,
+    ) -> Result<Round, ContractError> {
+        if !config.auction {
+            return Err(ContractError::InvalidConfiguration { 
+                msg: "Auction mode is not enabled".to_string() 
+            });
+        }
+
+        if !state.is_active || state.is_paused {
+            return Err(ContractError::TontineNotStarted);
+        }
+
+        Self::assert_active_member(storage, sender)?;
+
+        let round = get_current_round(storage)?;
+        if round.state != RoundState::Active {
+            return Err(ContractError::RoundNotActive);
+        }
+
+        // Only members still waiting for their payout can bid
+        let rotation = ROTATION.load(storage)?;
+        let slot = (round.round_number - 1) as usize;
+        if !rotation[slot..].contains(sender) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Member has already received a payout".to_string() 
+            });
+        }
+
+        Ok(round)
+    }
+
+    // The discount must leave something of a full pot
+    fn validate_discount(storage: &dyn Storage, config: &Config, discount: &str) -> Result<Uint128, ContractError> {
+        let discount = validate_amount(discount)?;
+        let full_pot = config.contribution_amount.saturating_sub(config.protocol_fees)
+            * Uint128::from(ROTATION.load(storage)?.len() as u64);
+        if discount >= full_pot {
+            return Err(ContractError::InvalidAmount { 
+                msg: format!("Discount must be below the pot of {}", full_pot) 
+            });
+        }
+        Ok(discount)
+    }
+
+    pub fn commit_bid(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        hash: HexBinary,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+
+        // The bond is only attached when one is configured
+        let bond = if config.bid_bond.is_zero() && info.funds.is_empty() {
+            Uint128::zero()
+        } else {
+            Self::native_payment(&config, &info)?
+        };
+        Self::record_bid_commitment(deps, env, info.sender, hash, bond)
+    }
+
+    // Stores a sealed bid, with its bond paid in native or CW20 tokens
+    fn record_bid_commitment(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        hash: HexBinary,
+        bond: Uint128,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+        let state = get_tontine_state(deps.storage)?;
+
+        if !config.sealed_bids {
+            return Err(ContractError::InvalidConfiguration { 
+                msg: "Sealed bids are not enabled".to_string() 
+            });
+        }
+
+        // Commitments close when the reveal window opens
+        let round = Self::assert_can_bid(deps.storage, &config, &state, &sender)?;
+        if env.block.time > round.deadline.minus_seconds(config.time_guards) {
+            return Err(ContractError::RoundDeadlineExceeded);
+        }
+
+        if hash.len() != 32 {
+            return Err(ContractError::InvalidAmount { 
+                msg: "Bid hash must be a SHA-256 hash".to_string() 
+            });
+        }
+        if bond != config.bid_bond {
+            return Err(ContractError::InvalidDeposit { 
+                msg: format!("Sealed bids require a bond of {}, received {}", config.bid_bond, bond) 
+            });
+        }
+        if BID_COMMITMENTS.has(deps.storage, (round.round_number, &sender)) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: format!("{} already committed a bid for round {}", sender, round.round_number) 
+            });
+        }
+
+        BID_COMMITMENTS.save(deps.storage, (round.round_number, &sender), &BidCommitment {
+            hash,
+            bond,
+            timestamp: env.block.time,
+            revealed: false,
+        })?;
+
+        let response = Response::new()
+            .add_attribute("method", "commit_bid")
+            .add_attribute("round", round.round_number.to_string())
+            .add_attribute("member", sender.to_string())
+            .add_attribute("bond", bond.to_string());
+
+        Ok(response)
+    }
+
+    pub fn reveal_bid(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        discount: String,
+        salt: String,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+        let state = get_tontine_state(deps.storage)?;
+
+        // Bids are revealed in the time_guards window before the deadline
+        let round = Self::assert_can_bid(deps.storage, &config, &state, &info.sender)?;
+        if env.block.time <= round.deadline.minus_seconds(config.time_guards) {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Reveal window has not opened".to_string() 
+            });
+        }
+        if env.block.time > round.deadline {
+            return Err(ContractError::RoundDeadlineExceeded);
+        }
+
+        let key = (round.round_number, &info.sender);
+        let mut commitment = BID_COMMITMENTS.may_load(deps.storage, key)?.ok_or_else(|| {
+            ContractError::InvalidMemberManagement { 
+                msg: format!("No sealed bid from {} for round {}", info.sender, round.round_number) 
+            }
+        })?;
+        if commitment.revealed {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Bid already revealed".to_string() 
+            });
+        }
+        if Sha256::digest(format!("{}:{}", discount, salt).as_bytes()).as_slice() != commitment.hash.as_slice() {
+            return Err(ContractError::InvalidMemberManagement { 
+                msg: "Discount and salt do not match the sealed bid".to_string() 
+            });
+        }
+        let discount = Self::validate_discount(deps.storage, &config, &discount)?;
+
+        // Ties go to the earliest commitment
+        commitment.revealed = true;
+        BID_COMMITMENTS.save(deps.storage, key, &commitment)?;
+        BIDS.save(deps.storage, key, &Bid {
+            discount,
+            timestamp: commitment.timestamp,
+        })?;
+
+        let mut response = Response::new()
+            .add_attribute("method", "reveal_bid")
+            .add_attribute("round", round.round_number.to_string())
+            .add_attribute("member", info.sender.to_string())
+            .add_attribute("discount", discount);
+
+        // Revealing releases the bond
+        if !commitment.bond.is_zero() {
+            response = response.add_message(Self::transfer_msg(&config.denom, &info.sender, commitment.bond)?);
+        }
+
+        Ok(response)
+    }
+
+    // Bonds of sealed bids left unrevealed join the collected penalties
+    fn forfeit_unrevealed_bonds(storage: &mut dyn Storage, round_number: u64) -> Result<Uint128, ContractError> {
+        let forfeited = BID_COMMITMENTS
+            .prefix(round_number)
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, commitment)| if commitment.revealed { Uint128::zero() } else { commitment.bond }))
+            .sum::<StdResult<Uint128>>()?;
+
+        if !forfeited.is_zero() {
+            let collected = COLLECTED_PENALTIES.may_load(storage)?.unwrap_or_default();
+            COLLECTED_PENALTIES.save(storage, &(collected + forfeited))?;
+        }
+
+        Ok(forfeited)
     }
 
     pub fn declare_late(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         member: String,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
         
-        // Only admin or arbitrator can declare members late
-        if info.sender != config.admin && info.sender != config.arbitrator {
+        // Only admin, operators or arbitrators can declare members late
+        if !Self::has_role(deps.storage, &info.sender, Role::Operator)? && !config.arbitrators.contains(&info.sender) {
             return Err(ContractError::Unauthorized { 
-                msg: "Only admin or arbitrator can declare members late".to_string() 
+                msg: "Only admin, operators or arbitrators can declare members late".to_string() 
             });
         }
 
@@ -606,20 +2168,54 @@ impl ExecuteHandler {
         let member_addr = deps.api.addr_validate(&member)?;
         
         // Check if member exists
-        if !members().has(deps.storage, member_addr.as_str()) {
-            return Err(ContractError::MemberNotFound { address: member.clone() });
+        let mut member_data = get_member(deps.storage, &member_addr)?;
+
+        // Member can only be declared late once the round deadline and the time_guards
+        // grace window have passed without a deposit
+        let round = get_current_round(deps.storage)?;
+        if round.state != RoundState::Active {
+            return Err(ContractError::RoundNotActive);
+        }
+        if env.block.time <= round.deadline.plus_seconds(config.time_guards) {
+            return Err(ContractError::RoundDeadlineNotReached);
+        }
+        if has_deposited(deps.storage, round.round_number, &member_addr) {
+            return Err(ContractError::MemberAlreadyContributed);
+        }
+        if LATE_PENALTIES.has(deps.storage, (round.round_number, &member_addr)) {
+            return Err(ContractError::MemberIsLate);
         }
 
-        let response = Response::new()
+        member_data.is_late = true;
+        members().save(deps.storage, member_addr.as_str(), &member_data)?;
+
+        // Record the configured late penalty
+        let penalty_amount = Self::late_penalty_for(&config, round.deadline, env.block.time);
+        let penalty_id = Self::assess_late_penalty(
+            deps.storage,
+            &env,
+            &member_addr,
+            round.round_number,
+            penalty_amount,
+        )?;
+
+        let mut response = Response::new()
             .add_attribute("method", "declare_late")
-            .add_attribute("member", member);
+            .add_attribute("member", member)
+            .add_attribute("round", round.round_number.to_string());
+
+        if let Some(penalty_id) = penalty_id {
+            response = response
+                .add_attribute("penalty_id", penalty_id.to_string())
+                .add_attribute("penalty", penalty_amount.to_string());
+        }
 
         Ok(response)
     }
 
     pub fn apply_penalty(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         member: String,
         amount: String,
@@ -627,7 +2223,7 @@ impl ExecuteHandler {
         let config = get_config(deps.storage)?;
         
         // Only admin or arbitrator can apply penalties
-        if info.sender != config.admin && info.sender != config.arbitrator {
+        if !Self::is_admin(deps.storage, &info.sender) && !config.arbitrators.contains(&info.sender) {
             return Err(ContractError::Unauthorized { 
                 msg: "Only admin or arbitrator can apply penalties".to_string() 
             });
@@ -642,44 +2238,175 @@ impl ExecuteHandler {
         }
 
         // Parse penalty amount
-        let _penalty_amount = validate_amount(&amount)?;
+        let penalty_amount = validate_amount(&amount)?;
+        if penalty_amount.is_zero() {
+            return Err(ContractError::InvalidPenaltyApplication { 
+                msg: "Penalty amount must be greater than zero".to_string() 
+            });
+        }
+
+        let penalty_id = Self::assess_penalty(
+            deps.storage,
+            &env,
+            &member_addr,
+            penalty_amount,
+            format!("Applied by {}", info.sender),
+        )?;
 
         let response = Response::new()
             .add_attribute("method", "apply_penalty")
             .add_attribute("member", member)
-            .add_attribute("amount", amount);
+            .add_attribute("amount", amount)
+            .add_attribute("penalty_id", penalty_id.to_string());
 
         Ok(response)
     }
 
+    // Late penalty owed against a round deadline, scaled per started day when configured
+    fn late_penalty_for(config: &Config, deadline: Timestamp, now: Timestamp) -> Uint128 {
+        let seconds_late = now.seconds().saturating_sub(deadline.seconds());
+        if seconds_late == 0 {
+            return Uint128::zero();
+        }
+        if !config.scale_late_penalty {
+            return config.late_penalty;
+        }
+
+        let days_late = seconds_late.div_ceil(SECONDS_PER_DAY);
+        config.late_penalty * Uint128::from(days_late)
+    }
+
+    // Records at most one late penalty per member and round
+    fn assess_late_penalty(
+        storage: &mut dyn Storage,
+        env: &Env,
+        member_addr: &Addr,
+        round_number: u64,
+        amount: Uint128,
+    ) -> Result<Option<u64>, ContractError> {
+        if amount.is_zero() || LATE_PENALTIES.has(storage, (round_number, member_addr)) {
+            return Ok(None);
+        }
+
+        let penalty_id = Self::assess_penalty(
+            storage,
+            env,
+            member_addr,
+            amount,
+            format!("Late for round {}", round_number),
+        )?;
+        LATE_PENALTIES.save(storage, (round_number, member_addr), &penalty_id)?;
+
+        Ok(Some(penalty_id))
+    }
+
+    // Adds a penalty to the ledger and to the member's outstanding total
+    fn assess_penalty(
+        storage: &mut dyn Storage,
+        env: &Env,
+        member_addr: &Addr,
+        amount: Uint128,
+        reason: String,
+    ) -> Result<u64, ContractError> {
+        let mut member = get_member(storage, member_addr)?;
+        member.penalties += amount;
+        members().save(storage, member_addr.as_str(), &member)?;
+
+        let penalty_id = PENALTY_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+        PENALTY_COUNT.save(storage, &penalty_id)?;
+
+        let penalty = Penalty {
+            member: member_addr.clone(),
+            amount,
+            reason,
+            timestamp: env.block.time,
+            is_paid: false,
+            payment_time: None,
+        };
+        PENALTIES.save(storage, (member_addr, penalty_id), &penalty)?;
+        update_totals(storage, |totals| totals.penalties_assessed += amount)?;
+
+        Ok(penalty_id)
+    }
+
     pub fn pay_penalty(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         member: String,
     ) -> Result<Response, ContractError> {
-        let _config = get_config(deps.storage)?;
-        
+        let config = get_config(deps.storage)?;
+        let paid = Self::native_payment(&config, &info)?;
+        Self::settle_penalty(deps, env, info.sender, member, paid)
+    }
+
+    // Settles a member's outstanding penalties paid with either native or CW20 tokens
+    fn settle_penalty(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        member: String,
+        paid: Uint128,
+    ) -> Result<Response, ContractError> {
         // Only the member themselves can pay their penalty
         let member_addr = deps.api.addr_validate(&member)?;
-        if info.sender != member_addr {
+        if sender != member_addr {
             return Err(ContractError::Unauthorized { 
                 msg: "Only the member can pay their own penalty".to_string() 
             });
         }
 
         // Check if member exists
-        if !members().has(deps.storage, member_addr.as_str()) {
-            return Err(ContractError::MemberNotFound { address: member.clone() });
+        let mut member_data = get_member(deps.storage, &member_addr)?;
+
+        // Payment must cover the outstanding penalties exactly
+        if member_data.penalties.is_zero() {
+            return Err(ContractError::InvalidPenaltyApplication { 
+                msg: "No outstanding penalties".to_string() 
+            });
+        }
+        if paid != member_data.penalties {
+            return Err(ContractError::InvalidAmount { 
+                msg: format!("Outstanding penalties are {}, received {}", member_data.penalties, paid) 
+            });
+        }
+
+        // Mark every outstanding penalty in the ledger as paid
+        let unpaid: Vec<(u64, Penalty)> = PENALTIES
+            .prefix(&member_addr)
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| item.as_ref().map_or(true, |(_, penalty)| !penalty.is_paid))
+            .collect::<StdResult<_>>()?;
+
+        for (penalty_id, mut penalty) in unpaid.iter().cloned() {
+            penalty.is_paid = true;
+            penalty.payment_time = Some(env.block.time);
+            PENALTIES.save(deps.storage, (&member_addr, penalty_id), &penalty)?;
         }
+        let settled: Uint128 = unpaid.iter().map(|(_, penalty)| penalty.amount).sum();
+        update_totals(deps.storage, |totals| totals.penalties_paid += settled)?;
+
+        let mut collected = COLLECTED_PENALTIES.may_load(deps.storage)?.unwrap_or_default();
+        collected += paid;
+        COLLECTED_PENALTIES.save(deps.storage, &collected)?;
+
+        // Clearing the penalties unblocks the member's deposits
+        member_data.penalties = Uint128::zero();
+        member_data.is_late = false;
+        members().save(deps.storage, member_addr.as_str(), &member_data)?;
 
         let response = Response::new()
             .add_attribute("method", "pay_penalty")
-            .add_attribute("member", member);
+            .add_attribute("member", member)
+            .add_attribute("amount", paid.to_string())
+            .add_attribute("penalties_settled", unpaid.len().to_string())
+            .add_attribute("payment_time", env.block.time.to_string());
 
         Ok(response)
     }
 
+    // Protocol treasury: the fees deducted from distributions go to the fee recipient, sent by
+    // the admin or treasurers or pulled by the fee recipient itself
     pub fn withdraw_fees(
         deps: DepsMut,
         _env: Env,
@@ -687,160 +2414,614 @@ impl ExecuteHandler {
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
         
-        // Only admin can withdraw fees
-        if info.sender != config.admin {
+        // Only admin, treasurers or the fee recipient can withdraw fees
+        if !Self::has_role(deps.storage, &info.sender, Role::Treasurer)? && info.sender != config.fee_recipient {
             return Err(ContractError::Unauthorized { 
-                msg: "Only admin can withdraw fees".to_string() 
+                msg: "Only admin, treasurers or the fee recipient can withdraw fees".to_string() 
+            });
+        }
+
+        let amount = get_accumulated_fees(deps.storage)?;
+        if amount.is_zero() {
+            return Err(ContractError::InvalidFeeCollection { 
+                msg: "No protocol fees to collect".to_string() 
             });
         }
 
+        let mut withdrawn = WITHDRAWN_FEES.may_load(deps.storage)?.unwrap_or_default();
+        withdrawn += amount;
+        WITHDRAWN_FEES.save(deps.storage, &withdrawn)?;
+        ACCUMULATED_FEES.save(deps.storage, &Uint128::zero())?;
+
         let response = Response::new()
-            .add_attribute("method", "withdraw_fees");
+            .add_message(Self::transfer_msg(&config.denom, &config.fee_recipient, amount)?)
+            .add_attribute("method", "withdraw_fees")
+            .add_attribute("recipient", config.fee_recipient.to_string())
+            .add_attribute("amount", amount.to_string());
 
         Ok(response)
     }
 
-    pub fn collect_protocol_fees(
+    // Organiser commission: penalties paid by members go to the admin
+    pub fn withdraw_penalties(
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
         
-        // Only admin can collect protocol fees
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can collect protocol fees".to_string() 
+        // Only admin or treasurers can withdraw penalties, which always go to the admin
+        Self::assert_role(deps.storage, &info.sender, Role::Treasurer, "Only admin or treasurers can withdraw penalties")?;
+        let admin = cw_ownable::get_ownership(deps.storage)?.owner.ok_or(ContractError::InvalidWithdrawal { 
+            msg: "Ownership has been renounced".to_string() 
+        })?;
+
+        let amount = COLLECTED_PENALTIES.may_load(deps.storage)?.unwrap_or_default();
+        if amount.is_zero() {
+            return Err(ContractError::InvalidWithdrawal { 
+                msg: "No collected penalties to withdraw".to_string() 
             });
         }
 
+        let mut withdrawn = WITHDRAWN_PENALTIES.may_load(deps.storage)?.unwrap_or_default();
+        withdrawn += amount;
+        WITHDRAWN_PENALTIES.save(deps.storage, &withdrawn)?;
+        COLLECTED_PENALTIES.save(deps.storage, &Uint128::zero())?;
+
         let response = Response::new()
-            .add_attribute("method", "collect_protocol_fees");
+            .add_message(Self::transfer_msg(&config.denom, &admin, amount)?)
+            .add_attribute("method", "withdraw_penalties")
+            .add_attribute("recipient", admin.to_string())
+            .add_attribute("amount", amount.to_string());
 
         Ok(response)
     }
 
-    pub fn resolve_dispute(
+    pub fn update_fee_recipient(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        fee_recipient: String,
+    ) -> Result<Response, ContractError> {
+        let mut config = get_config(deps.storage)?;
+
+        // Only admin can change the fee recipient
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can update the fee recipient")?;
+
+        config.fee_recipient = deps.api.addr_validate(&fee_recipient)?;
+        CONFIG.save(deps.storage, &config)?;
+
+        let response = Response::new()
+            .add_attribute("method", "update_fee_recipient")
+            .add_attribute("fee_recipient", fee_recipient);
+
+        Ok(response)
+    }
+
+    pub fn open_dispute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        reason: String,
+        round: u64,
+    ) -> Result<Response, ContractError> {
+        // Only active members can open disputes, and with them lock a round in escrow
+        Self::assert_active_member(deps.storage, &info.sender)?;
+
+        if reason.trim().is_empty() {
+            return Err(ContractError::InvalidDisputeResolution { 
+                msg: "Dispute reason cannot be empty".to_string() 
+            });
+        }
+
+        // Dispute must refer to an existing round
+        get_round(deps.storage, round)?;
+
+        // One dispute per member and round
+        if DISPUTES.has(deps.storage, (&info.sender, round)) {
+            return Err(ContractError::InvalidDisputeResolution { 
+                msg: format!("Dispute already opened for round {}", round) 
+            });
+        }
+
+        let dispute = Dispute {
+            member: info.sender.clone(),
+            round,
+            reason: reason.clone(),
+            timestamp: env.block.time,
+            status: DisputeStatus::Open,
+            resolution: None,
+            resolution_time: None,
+            appealed: false,
+        };
+        DISPUTES.save(deps.storage, (&info.sender, round), &dispute)?;
+
+        let mut response = Response::new()
+            .add_attribute("method", "open_dispute")
+            .add_attribute("member", info.sender.to_string())
+            .add_attribute("round", round.to_string())
+            .add_attribute("reason", reason.clone());
+
+        // Disputing a round that has not been paid out locks its balance in escrow
+        let disputed_round = get_round(deps.storage, round)?;
+        let mut escrow = get_escrow_state(deps.storage)?;
+        if disputed_round.state == RoundState::Active && !disputed_round.is_distributed && !escrow.is_locked {
+            escrow.is_locked = true;
+            escrow.locked_amount = disputed_round.balance;
+            escrow.lock_reason = reason;
+            escrow.lock_timestamp = Some(env.block.time);
+            escrow.locked_round = Some(round);
+            ESCROW_STATE.save(deps.storage, &escrow)?;
+
+            response = response
+                .add_attribute("escrow_locked", "true")
+                .add_attribute("locked_amount", escrow.locked_amount.to_string());
+        }
+
+        Ok(response)
+    }
+
+    pub fn review_dispute(
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
         member: String,
-        resolution: String,
+        round: u64,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
-        
-        // Only admin can resolve disputes
-        if info.sender != config.admin {
+
+        // Only admin or arbitrator can take disputes under review
+        if !Self::is_admin(deps.storage, &info.sender) && !config.arbitrators.contains(&info.sender) {
             return Err(ContractError::Unauthorized { 
-                msg: "Only admin can resolve disputes".to_string() 
+                msg: "Only admin or arbitrator can review disputes".to_string() 
+            });
+        }
+
+        let member_addr = deps.api.addr_validate(&member)?;
+        let mut dispute = get_dispute(deps.storage, &member_addr, round)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(ContractError::InvalidDisputeResolution { 
+                msg: format!("Dispute is {:?}, expected Open", dispute.status) 
             });
         }
 
+        dispute.status = DisputeStatus::UnderReview;
+        DISPUTES.save(deps.storage, (&member_addr, round), &dispute)?;
+
+        let response = Response::new()
+            .add_attribute("method", "review_dispute")
+            .add_attribute("member", member)
+            .add_attribute("round", round.to_string());
+
+        Ok(response)
+    }
+
+    pub fn resolve_dispute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        member: String,
+        round: u64,
+        resolution: String,
+    ) -> Result<Response, ContractError> {
+        // Only admin can resolve disputes
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can resolve disputes")?;
+
         // Validate member address
         let member_addr = deps.api.addr_validate(&member)?;
         
-        // Check if member exists
-        if !members().has(deps.storage, member_addr.as_str()) {
-            return Err(ContractError::MemberNotFound { address: member.clone() });
+        // Appealed disputes can only be decided by the arbitrator
+        let mut dispute = get_dispute(deps.storage, &member_addr, round)?;
+        match dispute.status {
+            DisputeStatus::Open | DisputeStatus::UnderReview if !dispute.appealed => {}
+            DisputeStatus::UnderReview => {
+                return Err(ContractError::InvalidDisputeResolution { 
+                    msg: "Appealed disputes must be arbitrated".to_string() 
+                });
+            }
+            _ => {
+                return Err(ContractError::InvalidDisputeResolution { 
+                    msg: format!("Dispute is already {:?}", dispute.status) 
+                });
+            }
         }
 
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolution = Some(resolution.clone());
+        dispute.resolution_time = Some(env.block.time);
+        DISPUTES.save(deps.storage, (&member_addr, round), &dispute)?;
+
         let response = Response::new()
             .add_attribute("method", "resolve_dispute")
             .add_attribute("member", member)
+            .add_attribute("round", round.to_string())
             .add_attribute("resolution", resolution);
 
+        // Resolving the dispute lets the escrowed round be distributed as usual
+        let escrow = get_escrow_state(deps.storage)?;
+        if escrow.is_locked && escrow.locked_round == Some(round) {
+            let config = get_config(deps.storage)?;
+            return Self::release_escrow(deps, env, &config, response, EscrowRelease::Beneficiary);
+        }
+
+        Ok(response)
+    }
+
+    pub fn appeal_dispute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        round: u64,
+    ) -> Result<Response, ContractError> {
+        let config = get_config(deps.storage)?;
+
+        // Only the member who opened the dispute can appeal, once
+        let mut dispute = get_dispute(deps.storage, &info.sender, round)?;
+        if dispute.status != DisputeStatus::Resolved || dispute.appealed {
+            return Err(ContractError::InvalidDisputeResolution { 
+                msg: "Only resolved disputes can be appealed".to_string() 
+            });
+        }
+
+        // Appeals must be lodged within time_guards of the resolution
+        if let Some(resolution_time) = dispute.resolution_time {
+            if env.block.time > resolution_time.plus_seconds(config.time_guards) {
+                return Err(ContractError::InvalidDisputeResolution { 
+                    msg: "Appeal window has closed".to_string() 
+                });
+            }
+        }
+
+        dispute.status = DisputeStatus::UnderReview;
+        dispute.appealed = true;
+        DISPUTES.save(deps.storage, (&info.sender, round), &dispute)?;
+
+        let response = Response::new()
+            .add_attribute("method", "appeal_dispute")
+            .add_attribute("member", info.sender.to_string())
+            .add_attribute("round", round.to_string());
+
         Ok(response)
     }
 
     pub fn arbitrate_dispute(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         member: String,
+        round: u64,
         decision: String,
+        release: Option<EscrowRelease>,
     ) -> Result<Response, ContractError> {
         let config = get_config(deps.storage)?;
         
-        // Only arbitrator can arbitrate disputes
-        if info.sender != config.arbitrator {
+        // Only arbitrators can arbitrate disputes
+        if !config.arbitrators.contains(&info.sender) {
             return Err(ContractError::Unauthorized { 
-                msg: "Only arbitrator can arbitrate disputes".to_string() 
+                msg: "Only arbitrators can arbitrate disputes".to_string() 
             });
         }
 
+        // Funds of a finished tontine have already been settled
+        if get_tontine_state(deps.storage)?.is_finished {
+            return Err(ContractError::TontineAlreadyFinished);
+        }
+
         // Validate member address
         let member_addr = deps.api.addr_validate(&member)?;
         
-        // Check if member exists
-        if !members().has(deps.storage, member_addr.as_str()) {
-            return Err(ContractError::MemberNotFound { address: member.clone() });
+        // Arbitration is final and closes the dispute
+        let mut dispute = get_dispute(deps.storage, &member_addr, round)?;
+        if dispute.status == DisputeStatus::Closed {
+            return Err(ContractError::InvalidDisputeResolution { 
+                msg: "Dispute is already closed".to_string() 
+            });
         }
 
+        // Record the vote, replacing any earlier vote from the same arbitrator
+        let vote = ArbitrationVote {
+            decision: decision.clone(),
+            release: release.unwrap_or(EscrowRelease::Beneficiary),
+            timestamp: env.block.time,
+        };
+        ARBITRATION_VOTES.save(deps.storage, (&member_addr, round, &info.sender), &vote)?;
+
+        // Count sitting arbitrators who voted for the same outcome
+        let votes = ARBITRATION_VOTES
+            .prefix((&member_addr, round))
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let agreeing = votes.iter()
+            .filter(|(arbitrator, other)| {
+                config.arbitrators.contains(arbitrator)
+                    && other.decision == vote.decision
+                    && other.release == vote.release
+            })
+            .count() as u64;
+
         let response = Response::new()
             .add_attribute("method", "arbitrate_dispute")
             .add_attribute("member", member)
-            .add_attribute("decision", decision);
+            .add_attribute("round", round.to_string())
+            .add_attribute("arbitrator", info.sender.to_string())
+            .add_attribute("decision", decision.clone())
+            .add_attribute("votes", agreeing.to_string())
+            .add_attribute("quorum", config.arbitration_quorum.to_string());
+
+        if agreeing < config.arbitration_quorum {
+            return Ok(response);
+        }
+
+        // Quorum reached, the decision closes the dispute
+        for (arbitrator, _) in votes {
+            ARBITRATION_VOTES.remove(deps.storage, (&member_addr, round, &arbitrator));
+        }
+
+        dispute.status = DisputeStatus::Closed;
+        dispute.resolution = Some(decision);
+        dispute.resolution_time = Some(env.block.time);
+        DISPUTES.save(deps.storage, (&member_addr, round), &dispute)?;
+
+        let response = response.add_attribute("decided", "true");
+
+        // The decision releases the escrow held for the disputed round
+        let escrow = get_escrow_state(deps.storage)?;
+        if escrow.is_locked && escrow.locked_round == Some(round) {
+            return Self::release_escrow(deps, env, &config, response, vote.release);
+        }
 
         Ok(response)
     }
 
-    pub fn finalize_tontine(
+    // Replaces an unavailable arbitrator, directly by the admin or once a majority
+    // of active members approved the same replacement
+    pub fn replace_arbitrator(
         deps: DepsMut,
-        env: Env,
+        _env: Env,
         info: MessageInfo,
+        arbitrator: String,
+        replacement: String,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
-        let mut state = get_tontine_state(deps.storage)?;
-        
-        // Only admin can finalize tontine
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can finalize tontine".to_string() 
+        let mut config = get_config(deps.storage)?;
+        let arbitrator_addr = deps.api.addr_validate(&arbitrator)?;
+        let replacement_addr = deps.api.addr_validate(&replacement)?;
+
+        let Some(index) = config.arbitrators.iter().position(|addr| *addr == arbitrator_addr) else {
+            return Err(ContractError::InvalidArbitration { 
+                msg: format!("{} is not an arbitrator", arbitrator) 
+            });
+        };
+        if config.arbitrators.contains(&replacement_addr) {
+            return Err(ContractError::InvalidArbitration { 
+                msg: format!("{} is already an arbitrator", replacement) 
             });
         }
 
-        // Check if tontine is active
-        if !state.is_active {
-            return Err(ContractError::TontineNotStarted);
+        let mut response = Response::new()
+            .add_attribute("method", "replace_arbitrator")
+            .add_attribute("arbitrator", arbitrator)
+            .add_attribute("replacement", replacement);
+
+        if !Self::is_admin(deps.storage, &info.sender) {
+            let member = get_member(deps.storage, &info.sender).map_err(|_| ContractError::Unauthorized { 
+                msg: "Only admin or members can replace arbitrators".to_string() 
+            })?;
+            if member.status != MemberStatus::Active {
+                return Err(ContractError::Unauthorized { 
+                    msg: "Only active members can approve an arbitrator replacement".to_string() 
+                });
+            }
+
+            ARBITRATOR_REPLACEMENTS.save(deps.storage, (&arbitrator_addr, &info.sender), &replacement_addr)?;
+
+            let active_members = active_member_count(deps.storage)?;
+            let mut approvals = 0u64;
+            for item in ARBITRATOR_REPLACEMENTS.prefix(&arbitrator_addr).range(deps.storage, None, None, Order::Ascending) {
+                let (approver, approved) = item?;
+                if approved == replacement_addr && get_member(deps.storage, &approver)?.status == MemberStatus::Active {
+                    approvals += 1;
+                }
+            }
+
+            response = response.add_attribute("approvals", approvals.to_string());
+            if approvals * 2 <= active_members {
+                return Ok(response);
+            }
         }
 
-        // Check if all rounds are completed
-        if state.current_round < state.total_rounds {
-            return Err(ContractError::InvalidStateUpdate { 
-                msg: "Cannot finalize before all rounds are completed".to_string() 
-            });
+        // Swap the arbitrator and drop approvals gathered against them
+        config.arbitrators[index] = replacement_addr;
+        CONFIG.save(deps.storage, &config)?;
+
+        let approvers = ARBITRATOR_REPLACEMENTS
+            .prefix(&arbitrator_addr)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for approver in approvers {
+            ARBITRATOR_REPLACEMENTS.remove(deps.storage, (&arbitrator_addr, &approver));
         }
 
-        // Finalize tontine
-        state.is_finished = true;
-        state.is_active = false;
-        TONTINE_STATE.save(deps.storage, &state)?;
+        Ok(response.add_attribute("replaced", "true"))
+    }
 
-        let response = Response::new()
-            .add_attribute("method", "finalize_tontine")
-            .add_attribute("finalization_time", env.block.time.to_string());
+    // Unlocks the escrowed round, settling it directly for refunds and splits
+    fn release_escrow(
+        deps: DepsMut,
+        env: Env,
+        config: &Config,
+        response: Response,
+        release: EscrowRelease,
+    ) -> Result<Response, ContractError> {
+        if get_tontine_state(deps.storage)?.is_finished {
+            return Err(ContractError::TontineAlreadyFinished);
+        }
+
+        let mut escrow = get_escrow_state(deps.storage)?;
+        let round_number = escrow.locked_round.ok_or(ContractError::InvalidEscrowState)?;
+        let mut round = get_round(deps.storage, round_number)?;
+
+        let beneficiary_percent = match release {
+            EscrowRelease::Beneficiary => None,
+            EscrowRelease::Refund => Some(0),
+            EscrowRelease::Split { beneficiary_percent } => {
+                if beneficiary_percent > 100 {
+                    return Err(ContractError::InvalidEscrowManagement { 
+                        msg: "Beneficiary share cannot exceed 100%".to_string() 
+                    });
+                }
+                Some(beneficiary_percent)
+            }
+        };
+
+        escrow.is_locked = false;
+        escrow.locked_amount = Uint128::zero();
+        escrow.lock_reason = String::new();
+        escrow.lock_timestamp = None;
+        escrow.locked_round = None;
+        ESCROW_STATE.save(deps.storage, &escrow)?;
+
+        let mut response = response
+            .add_attribute("escrow_released", round_number.to_string())
+            .add_attribute("release", format!("{:?}", release));
+
+        // Releasing to the beneficiary lets the round be distributed as usual
+        let Some(beneficiary_percent) = beneficiary_percent else {
+            return Ok(response);
+        };
+
+        // Beneficiary receives its share, contributors are refunded the rest pro rata
+        let beneficiary_share = round.balance.multiply_ratio(beneficiary_percent, 100u64);
+        let refund_total = round.balance - beneficiary_share;
+
+        let mut refunded = Uint128::zero();
+        for deposit in get_round_deposits(deps.storage, round_number)? {
+            let refund = refund_total.multiply_ratio(deposit.amount, round.balance);
+            if !refund.is_zero() {
+                response = response.add_message(Self::transfer_msg(&config.denom, &deposit.member, refund)?);
+                Self::deduct_contribution(deps.storage, &deposit.member, refund)?;
+                refunded += refund;
+            }
+        }
+
+        // Rounding dust goes to the beneficiary
+        let beneficiary_amount = round.balance - refunded;
+        if !beneficiary_amount.is_zero() {
+            response = response.add_message(Self::transfer_msg(&config.denom, &round.beneficiary, beneficiary_amount)?);
+            DISTRIBUTIONS.save(deps.storage, round_number, &Distribution {
+                round: round_number,
+                beneficiary: round.beneficiary.clone(),
+                amount: beneficiary_amount,
+                discount: Uint128::zero(),
+                advanced_from: None,
+                timestamp: env.block.time,
+            })?;
+        }
+
+        round.state = if beneficiary_amount.is_zero() {
+            RoundState::Failed
+        } else {
+            RoundState::Distributed
+        };
+        round.is_distributed = true;
+        round.distribution_time = Some(env.block.time);
+        ROUNDS.save(deps.storage, round_number, &round)?;
+        ESCROW_REFUNDED.save(deps.storage, round_number, &refunded)?;
+        update_totals(deps.storage, |totals| {
+            totals.distributions += beneficiary_amount;
+            totals.active_rounds = totals.active_rounds.saturating_sub(1);
+            if round.state == RoundState::Distributed {
+                totals.completed_rounds += 1;
+            }
+        })?;
+
+        response = response
+            .add_attribute("refunded", refunded.to_string())
+            .add_attribute("beneficiary_amount", beneficiary_amount.to_string());
+
+        // Settled rounds are closed, so move on when it was the current one
+        let mut state = get_tontine_state(deps.storage)?;
+        if state.is_active && state.current_round == round_number {
+            let next_round = Self::advance_round(deps.storage, &env, config, &mut state)?;
+            response = Self::add_next_round_attributes(response, next_round);
+        }
 
         Ok(response)
     }
 
-    pub fn migrate(
+    // Rebuilds the running totals from raw storage, naming the ones that had drifted
+    pub fn recompute_totals(
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
-        new_code_id: u64,
     ) -> Result<Response, ContractError> {
-        let config = get_config(deps.storage)?;
+        // Only admin can recompute totals
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can recompute totals")?;
+
+        let stored = get_totals(deps.storage)?;
+        let totals = compute_totals(deps.storage)?;
+        TOTALS.save(deps.storage, &totals)?;
+
+        let drifted: Vec<&str> = [
+            ("contributions", stored.contributions != totals.contributions),
+            ("distributions", stored.distributions != totals.distributions),
+            ("penalties_assessed", stored.penalties_assessed != totals.penalties_assessed),
+            ("penalties_paid", stored.penalties_paid != totals.penalties_paid),
+            ("member_count", stored.member_count != totals.member_count),
+            ("active_rounds", stored.active_rounds != totals.active_rounds),
+            ("completed_rounds", stored.completed_rounds != totals.completed_rounds),
+        ]
+        .into_iter()
+        .filter(|(_, drifted)| *drifted)
+        .map(|(name, _)| name)
+        .collect();
+        // Attribute values cannot be empty
+        let drifted = if drifted.is_empty() { "none".to_string() } else { drifted.join(",") };
+
+        let response = Response::new()
+            .add_attribute("method", "recompute_totals")
+            .add_attribute("drifted", drifted)
+            .add_attribute("contributions", totals.contributions.to_string())
+            .add_attribute("distributions", totals.distributions.to_string())
+            .add_attribute("penalties_assessed", totals.penalties_assessed.to_string())
+            .add_attribute("penalties_paid", totals.penalties_paid.to_string())
+            .add_attribute("member_count", totals.member_count.to_string());
+
+        Ok(response)
+    }
+
+    pub fn finalize_tontine(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let state = get_tontine_state(deps.storage)?;
         
-        // Only admin can migrate contract
-        if info.sender != config.admin {
-            return Err(ContractError::Unauthorized { 
-                msg: "Only admin can migrate contract".to_string() 
+        // Only admin can finalize tontine
+        Self::assert_admin(deps.storage, &info.sender, "Only admin can finalize tontine")?;
+
+        // Distributing the last round finishes the tontine, so a running one still has rounds to
+        // pay out; CloseEarly is the way to stop it before then
+        if !state.is_finished {
+            if !state.is_active {
+                return Err(ContractError::TontineNotStarted);
+            }
+            return Err(ContractError::InvalidStateUpdate { 
+                msg: "Cannot finalize before all rounds are completed".to_string() 
             });
         }
 
+        // A tontine closed early is settled through refunds instead
+        let completed = ROUNDS.may_load(deps.storage, state.total_rounds)?
+            .is_some_and(|round| round.is_distributed);
+        if !completed {
+            return Err(ContractError::TontineAlreadyFinished);
+        }
+
+        // Finalizing a completed tontine changes nothing and can be repeated
+        let finalization_time = state.last_round_time.unwrap_or(env.block.time);
         let response = Response::new()
-            .add_attribute("method", "migrate")
-            .add_attribute("new_code_id", new_code_id.to_string());
+            .add_attribute("method", "finalize_tontine")
+            .add_attribute("finalization_time", finalization_time.to_string());
 
         Ok(response)
     }
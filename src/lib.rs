@@ -4,6 +4,7 @@ pub mod msg;
 pub mod state;
 pub mod execute;
 pub mod query;
+pub mod migrate;
 
 
 use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
@@ -11,10 +12,10 @@ use cw2::set_contract_version;
 
 use crate::contract::Contract;
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
-const CONTRACT_NAME: &str = "crates.io:tontine-contract";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = "crates.io:tontine-contract";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[entry_point]
 pub fn instantiate(
@@ -45,13 +46,19 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     contract.query(deps, msg)
 }
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract = Contract::new();
+    contract.migrate(deps, env, msg)
+}
+
 // CW20 tokens are handled through the execute entry point
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, Addr};
+    use cosmwasm_std::coins;
 
     #[test]
     fn test_instantiate() {
@@ -65,10 +72,21 @@ mod tests {
             contribution_amount: "1000".to_string(),
             round_frequency: 86400, // 1 day in seconds
             beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+            payout_order: None,
             late_penalty: "50".to_string(),
             protocol_fees: "10".to_string(),
             arbitrator: "arbitrator".to_string(),
+            arbitrators: None,
+            arbitration_quorum: None,
             time_guards: 3600, // 1 hour in seconds
+            max_members: None,
+            refund_overpayment: None,
+            cw20_token: None,
+            scale_late_penalty: None,
+            auction: None,
+            sealed_bids: None,
+            bid_bond: None,
+            fee_recipient: None,
         };
 
         let result = instantiate(deps.as_mut(), env, info, msg);
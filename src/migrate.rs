@@ -0,0 +1,241 @@
+use cosmwasm_std::{Api, DepsMut, Env, Response, Storage, Uint128};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
+
+use crate::error::ContractError;
+use crate::msg::{Denom, MigrateMsg, PayoutOrder};
+use crate::state::{
+    compute_totals, members, Config, CONFIG, COLLECTED_PENALTIES, DEPOSITS, DISPUTES, DISTRIBUTIONS, ESCROW_STATE, MEMBER_CONTRIBUTIONS, PENALTY_COUNT,
+    ROTATION, ROUNDS, TONTINE_STATE, TOTALS, WITHDRAWN_FEES, WITHDRAWN_PENALTIES,
+};
+use crate::{CONTRACT_NAME, CONTRACT_VERSION};
+
+pub struct MigrateHandler;
+
+impl MigrateHandler {
+    pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+        let stored = get_contract_version(deps.storage)?;
+
+        // Only tontine contracts can be migrated to this code
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::InvalidMigration { 
+                msg: format!("Cannot migrate from contract {}", stored.contract) 
+            });
+        }
+
+        let stored_version = Self::parse_version(&stored.version)?;
+        let new_version = Self::parse_version(CONTRACT_VERSION)?;
+        if stored_version > new_version {
+            return Err(ContractError::InvalidMigration { 
+                msg: format!("Cannot downgrade from {} to {}", stored_version, new_version) 
+            });
+        }
+
+        // Rewrite storage left by the deployed 0.1.x contracts
+        if stored_version < Version::new(0, 2, 0) {
+            v0_1::migrate(deps.storage, deps.api)?;
+        }
+
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        let response = Response::new()
+            .add_attribute("method", "migrate")
+            .add_attribute("from_version", stored_version.to_string())
+            .add_attribute("to_version", new_version.to_string());
+
+        Ok(response)
+    }
+
+    fn parse_version(version: &str) -> Result<Version, ContractError> {
+        Version::parse(version).map_err(|_| ContractError::InvalidMigration { 
+            msg: format!("Invalid contract version: {}", version) 
+        })
+    }
+}
+
+// Storage layout of 0.1.x contracts
+mod v0_1 {
+    use super::*;
+    use cosmwasm_std::{Addr, Order, StdResult, Timestamp};
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
+
+    use crate::msg::{DisputeStatus, RoundState};
+    use crate::state::{Deposit, Member};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Config {
+        pub admin: Addr,
+        pub token_denom: String,
+        pub contribution_amount: Uint128,
+        pub round_frequency: u64,
+        pub beneficiaries: Vec<Addr>,
+        pub late_penalty: Uint128,
+        pub protocol_fees: Uint128,
+        pub arbitrator: Addr,
+        pub time_guards: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct EscrowState {
+        pub is_locked: bool,
+        pub locked_amount: Uint128,
+        pub lock_reason: String,
+        pub lock_timestamp: Option<Timestamp>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Distribution {
+        pub round: u64,
+        pub beneficiary: Addr,
+        pub amount: Uint128,
+        pub timestamp: Timestamp,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Round {
+        pub round_number: u64,
+        pub state: RoundState,
+        pub balance: Uint128,
+        pub beneficiary: Addr,
+        pub deadline: Timestamp,
+        pub deposits: Vec<Deposit>,
+        pub is_distributed: bool,
+        pub distribution_time: Option<Timestamp>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct Dispute {
+        pub member: Addr,
+        pub reason: String,
+        pub timestamp: Timestamp,
+        pub status: DisputeStatus,
+        pub resolution: Option<String>,
+        pub resolution_time: Option<Timestamp>,
+    }
+
+    const CONFIG: Item<Config> = Item::new("config");
+    const ESCROW_STATE: Item<EscrowState> = Item::new("escrow_state");
+    const DISTRIBUTIONS: Map<u64, Distribution> = Map::new("distributions");
+    const ROUNDS: Map<u64, Round> = Map::new("rounds");
+    const DISPUTES: Map<(&Addr, u64), Dispute> = Map::new("disputes");
+    const MEMBERS: Map<&str, Member> = Map::new("members");
+
+    // 0.2.0 replaced token_denom with a denom, moved the admin to the contract owner,
+    // turned the arbitrator into a panel, stored the payout rotation separately,
+    // added fee and penalty settings, moved deposits out of their round into a map
+    // keyed by (round, member), indexed members by status and registration time,
+    // recorded the round of each dispute and started tracking withdrawals, collected
+    // penalties, penalty ids, distribution discounts, running totals and each
+    // member's running contribution total
+    pub fn migrate(storage: &mut dyn Storage, api: &dyn Api) -> Result<(), ContractError> {
+        let legacy = CONFIG.load(storage)?;
+        // The admin moved to the cw-ownable contract owner
+        cw_ownable::initialize_owner(storage, api, legacy.admin.as_str())?;
+
+        let config = super::Config {
+            fee_recipient: legacy.admin,
+            denom: Denom::Native(legacy.token_denom),
+            contribution_amount: legacy.contribution_amount,
+            round_frequency: legacy.round_frequency,
+            beneficiaries: legacy.beneficiaries,
+            payout_order: PayoutOrder::Fixed,
+            late_penalty: legacy.late_penalty,
+            protocol_fees: legacy.protocol_fees,
+            arbitrators: vec![legacy.arbitrator],
+            arbitration_quorum: 1,
+            time_guards: legacy.time_guards,
+            max_members: None,
+            refund_overpayment: false,
+            scale_late_penalty: false,
+            auction: false,
+            sealed_bids: false,
+            bid_bond: Uint128::zero(),
+        };
+        super::CONFIG.save(storage, &config)?;
+
+        // Started tontines paid out in the order of the beneficiaries
+        if TONTINE_STATE.may_load(storage)?.is_some_and(|state| state.current_round > 0) {
+            ROTATION.save(storage, &config.beneficiaries)?;
+        }
+
+        let legacy = DISTRIBUTIONS
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (round, distribution) in legacy {
+            super::DISTRIBUTIONS.save(storage, round, &crate::state::Distribution {
+                round: distribution.round,
+                beneficiary: distribution.beneficiary,
+                amount: distribution.amount,
+                discount: Uint128::zero(),
+                advanced_from: None,
+                timestamp: distribution.timestamp,
+            })?;
+        }
+
+        let legacy = ESCROW_STATE.load(storage)?;
+        super::ESCROW_STATE.save(storage, &crate::state::EscrowState {
+            is_locked: legacy.is_locked,
+            locked_amount: legacy.locked_amount,
+            lock_reason: legacy.lock_reason,
+            lock_timestamp: legacy.lock_timestamp,
+            locked_round: None,
+        })?;
+
+        let legacy = ROUNDS
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (round_number, round) in legacy {
+            for deposit in &round.deposits {
+                DEPOSITS.save(storage, (round_number, &deposit.member), deposit)?;
+                MEMBER_CONTRIBUTIONS.update(storage, &deposit.member, |total| -> StdResult<_> {
+                    Ok(total.unwrap_or_default() + deposit.amount)
+                })?;
+            }
+            super::ROUNDS.save(storage, round_number, &crate::state::Round {
+                round_number: round.round_number,
+                state: round.state,
+                balance: round.balance,
+                beneficiary: round.beneficiary,
+                deadline: round.deadline,
+                deposit_count: round.deposits.len() as u64,
+                is_distributed: round.is_distributed,
+                distribution_time: round.distribution_time,
+            })?;
+        }
+
+        let legacy = DISPUTES
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for ((member, round), dispute) in legacy {
+            super::DISPUTES.save(storage, (&member, round), &crate::state::Dispute {
+                member: dispute.member,
+                round,
+                reason: dispute.reason,
+                timestamp: dispute.timestamp,
+                status: dispute.status,
+                resolution: dispute.resolution,
+                resolution_time: dispute.resolution_time,
+                appealed: false,
+            })?;
+        }
+
+        // Saving through the indexed map writes the index entries
+        let legacy = MEMBERS
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (address, member) in legacy {
+            members().save(storage, address.as_str(), &member)?;
+        }
+
+        WITHDRAWN_FEES.save(storage, &Uint128::zero())?;
+        COLLECTED_PENALTIES.save(storage, &Uint128::zero())?;
+        WITHDRAWN_PENALTIES.save(storage, &Uint128::zero())?;
+        PENALTY_COUNT.save(storage, &0)?;
+
+        let totals = compute_totals(storage)?;
+        TOTALS.save(storage, &totals)?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Timestamp;
+use cosmwasm_std::{Addr, HexBinary, Timestamp};
+use cw20::Cw20ReceiveMsg;
+use cw_ownable::cw_ownable;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -8,29 +10,77 @@ pub struct InstantiateMsg {
     pub contribution_amount: String,
     pub round_frequency: u64, // in seconds
     pub beneficiaries: Vec<String>,
+    // How the payout rotation is built at start, defaults to the beneficiaries order
+    pub payout_order: Option<PayoutOrder>,
     pub late_penalty: String,
     pub protocol_fees: String,
     pub arbitrator: String,
+    // Further arbitrators sitting on the panel with arbitrator
+    pub arbitrators: Option<Vec<String>>,
+    // Arbitrator votes needed to decide a dispute, defaults to a majority of the panel
+    pub arbitration_quorum: Option<u64>,
     pub time_guards: u64, // in seconds
+    // Cap on active members, further registrations join the waitlist
+    pub max_members: Option<u64>,
+    pub refund_overpayment: Option<bool>,
+    // CW20 contract used for contributions and payouts instead of token_denom
+    pub cw20_token: Option<String>,
+    // Multiply late_penalty by the number of started days late
+    pub scale_late_penalty: Option<bool>,
+    // Pay each round's pot to the eligible member bidding the largest discount
+    pub auction: Option<bool>,
+    // Auction bids are committed as hashes and revealed in the time_guards window before the deadline
+    pub sealed_bids: Option<bool>,
+    // Bond attached to each sealed bid, forfeited if the bid is not revealed
+    pub bid_bond: Option<String>,
+    // Receiver of protocol fees, defaults to admin
+    pub fee_recipient: Option<String>,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_ownable]
 #[cw_serde]
 pub enum ExecuteMsg {
     // Member management
     RegisterMember { address: String },
     RemoveMember { address: String },
     ReplaceMember { old_address: String, new_address: String },
+    ExcludeMember { address: String },
+    RequestToJoin {},
+    ApproveJoin { address: String },
+    RejectJoin { address: String },
+    LeaveWaitlist {},
+    
+    // Invitations, identified by the SHA-256 hash of their code
+    CreateInvitation {
+        code_hash: HexBinary,
+        max_uses: u32,
+        expires_at: Option<Timestamp>,
+    },
+    RevokeInvitation { code_hash: HexBinary },
+    AcceptInvitation { code: String },
     
     // Tontine control
     StartTontine {},
     PauseTontine {},
+    UpdatePayoutOrder { payout_order: PayoutOrder },
+    // Random payout order: commit to sha256(secret), then reveal the secret
+    CommitSeed { commitment: HexBinary },
+    RevealSeed { secret: String },
     ResumeTontine {},
     CloseEarly { reason: String },
+    ClaimRefund {},
     
     // Round operations
     DepositContribution {},
     DistributeToBeneficiary {},
     AdvancePayment { beneficiary: String, discount: String },
+    PlaceBid { discount: String },
+    // Sealed bids: commit to sha256("{discount}:{salt}"), then reveal
+    CommitBid { hash: HexBinary },
+    RevealBid { discount: String, salt: String },
     
     // Penalty management
     DeclareLate { member: String },
@@ -39,52 +89,90 @@ pub enum ExecuteMsg {
     
     // Fee management
     WithdrawFees {},
-    CollectProtocolFees {},
+    WithdrawPenalties {},
+    UpdateFeeRecipient { fee_recipient: String },
     
     // Dispute resolution
-    ResolveDispute { member: String, resolution: String },
-    ArbitrateDispute { member: String, decision: String },
+    OpenDispute { reason: String, round: u64 },
+    ReviewDispute { member: String, round: u64 },
+    ResolveDispute { member: String, round: u64, resolution: String },
+    AppealDispute { round: u64 },
+    ArbitrateDispute {
+        member: String,
+        round: u64,
+        decision: String,
+        release: Option<EscrowRelease>,
+    },
+    ReplaceArbitrator { arbitrator: String, replacement: String },
+    
+    // Access control
+    GrantRole { address: String, role: Role },
+    RevokeRole { address: String, role: Role },
     
     // Finalization
     FinalizeTontine {},
+    // Rebuilds the running totals from raw storage
+    RecomputeTotals {},
     
-    // Migration
-    Migrate { new_code_id: u64 },
-    
+    // CW20 receive hook
+    Receive(Cw20ReceiveMsg),
+}
 
+// Messages embedded in a CW20 Send to this contract
+#[cw_serde]
+pub enum ReceiveMsg {
+    DepositContribution {},
+    PayPenalty { member: String },
+    RequestToJoin {},
+    CommitBid { hash: HexBinary },
 }
 
+// List queries return at most `limit` entries (default 10, max 30) after the
+// `start_after` key, along with the key to pass as `start_after` for the next page
 #[cw_serde]
 pub enum QueryMsg {
     // Configuration
     GetConfig {},
     GetAdmin {},
+    GetOwnership {},
+    GetRoles { start_after: Option<String>, limit: Option<u32> },
     GetArbitrator {},
     
     // Member information
-    GetMembers {},
+    GetMembers { start_after: Option<String>, limit: Option<u32> },
+    GetMembersByStatus { status: MemberStatus, start_after: Option<String>, limit: Option<u32> },
     GetMember { address: String },
     GetMemberStatus { address: String },
     GetMemberBalance { address: String },
     GetMemberPenalties { address: String },
+    GetJoinRequests { start_after: Option<String>, limit: Option<u32> },
+    GetWaitlist { start_after: Option<u64>, limit: Option<u32> },
+    GetWaitlistPosition { address: String },
+    GetInvitations { start_after: Option<HexBinary>, limit: Option<u32> },
     
     // Round information
     GetCurrentRound {},
     GetRoundInfo { round: u64 },
-    GetRoundDeposits { round: u64 },
+    GetRoundDeposits { round: u64, start_after: Option<String>, limit: Option<u32> },
     GetRoundState { round: u64 },
+    GetBids { round: u64, start_after: Option<String>, limit: Option<u32> },
+    GetBidCommitments { round: u64, start_after: Option<String>, limit: Option<u32> },
     
     // Financial information
     GetTontineBalance {},
     GetRoundBalance { round: u64 },
     GetAccumulatedFees {},
+    GetFeeInfo {},
     GetPendingPenalties {},
+    GetRefund { member: String },
     
     // Beneficiary information
     GetCurrentBeneficiary {},
     GetNextBeneficiary {},
     GetBeneficiariesList {},
     GetBeneficiarySchedule {},
+    GetRotation {},
+    GetRandomSeed { start_after: Option<String>, limit: Option<u32> },
     
     // Time information
     GetRoundDeadline { round: u64 },
@@ -92,14 +180,18 @@ pub enum QueryMsg {
     GetRoundFrequency {},
     
     // Historical information
-    GetDistributionHistory {},
-    GetPenaltyHistory {},
-    GetDepositHistory {},
+    GetDistributionHistory { start_after: Option<u64>, limit: Option<u32> },
+    // Penalties are keyed by (member, penalty id)
+    GetPenaltyHistory { start_after: Option<(String, u64)>, limit: Option<u32> },
+    // Deposits are keyed by (round, member)
+    GetDepositHistory { start_after: Option<(u64, String)>, limit: Option<u32> },
     
     // State information
     GetTontineState {},
     GetEscrowState {},
-    GetDisputeState {},
+    // Disputes are keyed by (member, round)
+    GetDisputeState { start_after: Option<(String, u64)>, limit: Option<u32> },
+    GetDispute { member: String, round: u64 },
     
     // Statistics
     GetMemberCount {},
@@ -114,13 +206,24 @@ pub enum QueryMsg {
 pub struct ConfigResponse {
     pub admin: String,
     pub token_denom: String,
+    pub denom: Denom,
     pub contribution_amount: String,
     pub round_frequency: u64,
     pub beneficiaries: Vec<String>,
+    pub payout_order: PayoutOrder,
     pub late_penalty: String,
     pub protocol_fees: String,
     pub arbitrator: String,
+    pub arbitrators: Vec<String>,
+    pub arbitration_quorum: u64,
     pub time_guards: u64,
+    pub max_members: Option<u64>,
+    pub refund_overpayment: bool,
+    pub scale_late_penalty: bool,
+    pub auction: bool,
+    pub sealed_bids: bool,
+    pub bid_bond: String,
+    pub fee_recipient: String,
     pub is_active: bool,
     pub is_paused: bool,
     pub is_finished: bool,
@@ -131,9 +234,20 @@ pub struct MemberResponse {
     pub address: String,
     pub status: MemberStatus,
     pub balance: String,
+    // Joining bond held for the member until the tontine is over
+    pub bond: String,
     pub penalties: String,
     pub last_contribution: Option<Timestamp>,
     pub is_late: bool,
+    // Round in which the member is paid, or was paid
+    pub payout_round: Option<u64>,
+    pub discount_credits: String,
+}
+
+#[cw_serde]
+pub struct MembersResponse {
+    pub members: Vec<MemberResponse>,
+    pub next_start_after: Option<String>,
 }
 
 #[cw_serde]
@@ -145,6 +259,7 @@ pub struct RoundResponse {
     pub deadline: Timestamp,
     pub deposits: Vec<DepositResponse>,
     pub is_distributed: bool,
+    pub distribution_time: Option<Timestamp>,
 }
 
 #[cw_serde]
@@ -155,6 +270,127 @@ pub struct DepositResponse {
     pub is_late: bool,
 }
 
+#[cw_serde]
+pub struct RoundDepositsResponse {
+    pub round: u64,
+    pub deposits: Vec<DepositResponse>,
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct BidResponse {
+    pub member: String,
+    pub discount: String,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct BidsResponse {
+    pub round: u64,
+    pub bids: Vec<BidResponse>,
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct BidCommitmentResponse {
+    pub member: String,
+    pub hash: HexBinary,
+    pub bond: String,
+    pub timestamp: Timestamp,
+    pub revealed: bool,
+}
+
+#[cw_serde]
+pub struct BidCommitmentsResponse {
+    pub round: u64,
+    pub commitments: Vec<BidCommitmentResponse>,
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct WaitlistEntryResponse {
+    pub address: String,
+    pub position: u64,
+    pub bond: String,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct WaitlistResponse {
+    pub entries: Vec<WaitlistEntryResponse>,
+    pub next_start_after: Option<u64>,
+}
+
+#[cw_serde]
+pub struct InvitationResponse {
+    pub code_hash: HexBinary,
+    pub max_uses: u32,
+    pub uses: u32,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct InvitationsResponse {
+    pub invitations: Vec<InvitationResponse>,
+    pub next_start_after: Option<HexBinary>,
+}
+
+#[cw_serde]
+pub struct JoinRequestResponse {
+    pub address: String,
+    pub deposit: String,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct JoinRequestsResponse {
+    pub requests: Vec<JoinRequestResponse>,
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct RoleAssignment {
+    pub address: String,
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub assignments: Vec<RoleAssignment>,
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct SeedCommitmentResponse {
+    pub member: String,
+    pub commitment: HexBinary,
+    pub secret: Option<String>,
+}
+
+#[cw_serde]
+pub struct RandomSeedResponse {
+    pub seed: Option<HexBinary>,
+    pub commitments: Vec<SeedCommitmentResponse>,
+    pub next_start_after: Option<String>,
+    pub commit_deadline: Option<Timestamp>,
+    pub reveal_deadline: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct RefundResponse {
+    pub member: String,
+    pub amount: String,
+}
+
+#[cw_serde]
+pub struct FeeInfoResponse {
+    pub fee_recipient: String,
+    pub accumulated_fees: String,
+    pub withdrawn_fees: String,
+    pub collected_penalties: String,
+    pub withdrawn_penalties: String,
+}
+
 #[cw_serde]
 pub struct TontineStateResponse {
     pub current_round: u64,
@@ -171,6 +407,7 @@ pub struct TontineStateResponse {
 #[cw_serde]
 pub struct DistributionHistoryResponse {
     pub distributions: Vec<DistributionResponse>,
+    pub next_start_after: Option<u64>,
 }
 
 #[cw_serde]
@@ -178,26 +415,32 @@ pub struct DistributionResponse {
     pub round: u64,
     pub beneficiary: String,
     pub amount: String,
+    pub discount: String,
+    pub advanced_from: Option<u64>,
     pub timestamp: Timestamp,
 }
 
 #[cw_serde]
 pub struct PenaltyHistoryResponse {
     pub penalties: Vec<PenaltyResponse>,
+    pub next_start_after: Option<(String, u64)>,
 }
 
 #[cw_serde]
 pub struct PenaltyResponse {
+    pub id: u64,
     pub member: String,
     pub amount: String,
     pub reason: String,
     pub timestamp: Timestamp,
     pub is_paid: bool,
+    pub payment_time: Option<Timestamp>,
 }
 
 #[cw_serde]
 pub struct DepositHistoryResponse {
     pub deposits: Vec<DepositResponse>,
+    pub next_start_after: Option<(u64, String)>,
 }
 
 #[cw_serde]
@@ -206,20 +449,31 @@ pub struct EscrowStateResponse {
     pub locked_amount: String,
     pub lock_reason: String,
     pub lock_timestamp: Option<Timestamp>,
+    pub locked_round: Option<u64>,
 }
 
 #[cw_serde]
 pub struct DisputeStateResponse {
     pub active_disputes: Vec<DisputeResponse>,
+    pub next_start_after: Option<(String, u64)>,
 }
 
 #[cw_serde]
 pub struct DisputeResponse {
     pub member: String,
+    pub round: u64,
     pub reason: String,
     pub timestamp: Timestamp,
     pub status: DisputeStatus,
     pub resolution: Option<String>,
+    pub resolution_time: Option<Timestamp>,
+    pub appealed: bool,
+}
+
+#[cw_serde]
+pub enum Denom {
+    Native(String),
+    Cw20(Addr),
 }
 
 #[cw_serde]
@@ -240,6 +494,38 @@ pub enum RoundState {
     Failed,
 }
 
+// Order in which members receive the payout
+#[cw_serde]
+pub enum PayoutOrder {
+    // Order of the configured beneficiaries
+    Fixed,
+    // Order in which members registered
+    Registration,
+    // Order supplied by the admin
+    Custom { order: Vec<String> },
+    // Order drawn at start from seeds committed and revealed by every member
+    Random,
+}
+
+// Operations the admin can delegate
+#[cw_serde]
+pub enum Role {
+    // Withdraws protocol fees and collected penalties
+    Treasurer,
+    // Distributes rounds and declares members late
+    Operator,
+    // Registers, removes and replaces members
+    Registrar,
+}
+
+// How the arbitrator releases a round held in escrow
+#[cw_serde]
+pub enum EscrowRelease {
+    Beneficiary,
+    Refund,
+    Split { beneficiary_percent: u64 },
+}
+
 #[cw_serde]
 pub enum DisputeStatus {
     Open,
@@ -254,6 +540,7 @@ pub struct StatisticsResponse {
     pub total_contributions: String,
     pub total_distributions: String,
     pub total_penalties: String,
+    pub total_penalties_paid: String,
     pub total_fees: String,
     pub active_rounds: u64,
     pub completed_rounds: u64,
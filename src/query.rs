@@ -1,18 +1,31 @@
 use cosmwasm_std::{
-    Binary, Deps, StdError, StdResult, Uint128
+    Addr, Binary, Deps, HexBinary, Order, StdError, StdResult, Uint128
 };
+use cw_storage_plus::Bound;
 use cosmwasm_std::to_json_binary as to_binary;
 use crate::msg::{
-    QueryMsg, ConfigResponse, MemberResponse, TontineStateResponse,
+    QueryMsg, ConfigResponse, Denom, FeeInfoResponse, MemberResponse, TontineStateResponse,
     DistributionHistoryResponse, PenaltyHistoryResponse, DepositHistoryResponse,
-    EscrowStateResponse, DisputeStateResponse, StatisticsResponse, RoundState,
-    DistributionResponse, PenaltyResponse, DepositResponse
+    EscrowStateResponse, DisputeStateResponse, StatisticsResponse,
+    DistributionResponse, PenaltyResponse, DepositResponse, DisputeResponse, DisputeStatus,
+    RefundResponse, RoleAssignment, RolesResponse, JoinRequestResponse, JoinRequestsResponse,
+    InvitationResponse, InvitationsResponse, WaitlistEntryResponse, WaitlistResponse,
+    RandomSeedResponse, SeedCommitmentResponse, BidResponse, BidsResponse,
+    BidCommitmentResponse, BidCommitmentsResponse, MemberStatus, MembersResponse, RoundDepositsResponse, RoundResponse
 };
 use crate::state::{
-    get_config, get_tontine_state, get_escrow_state, get_member, get_round,
-    get_current_round, get_accumulated_fees, members, DISTRIBUTIONS, PENALTIES
+    Deposit, Dispute, Member, Round, get_config, get_dispute, get_tontine_state, get_escrow_state, get_member, get_round,
+    get_current_round, get_accumulated_fees, get_round_deposits, get_totals, members, status_key, COLLECTED_PENALTIES, DEPOSITS, DISCOUNT_CREDITS, DISPUTES, DISTRIBUTIONS, MEMBER_CONTRIBUTIONS, PENALTIES,
+    waitlist_position, INVITATIONS, JOIN_REQUESTS, BIDS, BID_COMMITMENTS, RANDOM_SEED, REFUNDS, ROLES, ROTATION, SEED_COMMITMENTS, SEED_COMMIT_DEADLINE, WAITLIST, WITHDRAWN_FEES, WITHDRAWN_PENALTIES
 };
 
+// Default and maximum number of entries returned by list queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// Entries of a list query page and the key to continue after
+type Page<K, T> = (Vec<(K, T)>, Option<K>);
+
 pub struct QueryHandler;
 
 impl QueryHandler {
@@ -20,33 +33,55 @@ impl QueryHandler {
         match msg {
             QueryMsg::GetConfig {} => Self::get_config(deps),
             QueryMsg::GetAdmin {} => Self::get_admin(deps),
+            QueryMsg::GetOwnership {} => Self::get_ownership(deps),
+            QueryMsg::GetRoles { start_after, limit } => Self::get_roles(deps, start_after, limit),
             QueryMsg::GetArbitrator {} => Self::get_arbitrator(deps),
-            QueryMsg::GetMembers {} => Self::get_members(deps),
+            QueryMsg::GetMembers { start_after, limit } => Self::get_members(deps, start_after, limit),
+            QueryMsg::GetMembersByStatus { status, start_after, limit } => {
+                Self::get_members_by_status(deps, status, start_after, limit)
+            }
             QueryMsg::GetMember { address } => Self::get_member(deps, address),
             QueryMsg::GetMemberStatus { address } => Self::get_member_status(deps, address),
             QueryMsg::GetMemberBalance { address } => Self::get_member_balance(deps, address),
             QueryMsg::GetMemberPenalties { address } => Self::get_member_penalties(deps, address),
+            QueryMsg::GetJoinRequests { start_after, limit } => Self::get_join_requests(deps, start_after, limit),
+            QueryMsg::GetWaitlist { start_after, limit } => Self::get_waitlist(deps, start_after, limit),
+            QueryMsg::GetWaitlistPosition { address } => Self::get_waitlist_position(deps, address),
+            QueryMsg::GetInvitations { start_after, limit } => Self::get_invitations(deps, start_after, limit),
             QueryMsg::GetCurrentRound {} => Self::get_current_round(deps),
             QueryMsg::GetRoundInfo { round } => Self::get_round_info(deps, round),
-            QueryMsg::GetRoundDeposits { round } => Self::get_round_deposits(deps, round),
+            QueryMsg::GetRoundDeposits { round, start_after, limit } => {
+                Self::get_round_deposits(deps, round, start_after, limit)
+            }
             QueryMsg::GetRoundState { round } => Self::get_round_state(deps, round),
+            QueryMsg::GetBids { round, start_after, limit } => Self::get_bids(deps, round, start_after, limit),
+            QueryMsg::GetBidCommitments { round, start_after, limit } => {
+                Self::get_bid_commitments(deps, round, start_after, limit)
+            }
             QueryMsg::GetTontineBalance {} => Self::get_tontine_balance(deps),
             QueryMsg::GetRoundBalance { round } => Self::get_round_balance(deps, round),
             QueryMsg::GetAccumulatedFees {} => Self::get_accumulated_fees(deps),
+            QueryMsg::GetFeeInfo {} => Self::get_fee_info(deps),
             QueryMsg::GetPendingPenalties {} => Self::get_pending_penalties(deps),
+            QueryMsg::GetRefund { member } => Self::get_refund(deps, member),
             QueryMsg::GetCurrentBeneficiary {} => Self::get_current_beneficiary(deps),
             QueryMsg::GetNextBeneficiary {} => Self::get_next_beneficiary(deps),
             QueryMsg::GetBeneficiariesList {} => Self::get_beneficiaries_list(deps),
             QueryMsg::GetBeneficiarySchedule {} => Self::get_beneficiary_schedule(deps),
+            QueryMsg::GetRotation {} => Self::get_rotation(deps),
+            QueryMsg::GetRandomSeed { start_after, limit } => Self::get_random_seed(deps, start_after, limit),
             QueryMsg::GetRoundDeadline { round } => Self::get_round_deadline(deps, round),
             QueryMsg::GetTimeGuards {} => Self::get_time_guards(deps),
             QueryMsg::GetRoundFrequency {} => Self::get_round_frequency(deps),
-            QueryMsg::GetDistributionHistory {} => Self::get_distribution_history(deps),
-            QueryMsg::GetPenaltyHistory {} => Self::get_penalty_history(deps),
-            QueryMsg::GetDepositHistory {} => Self::get_deposit_history(deps),
+            QueryMsg::GetDistributionHistory { start_after, limit } => {
+                Self::get_distribution_history(deps, start_after, limit)
+            }
+            QueryMsg::GetPenaltyHistory { start_after, limit } => Self::get_penalty_history(deps, start_after, limit),
+            QueryMsg::GetDepositHistory { start_after, limit } => Self::get_deposit_history(deps, start_after, limit),
             QueryMsg::GetTontineState {} => Self::get_tontine_state(deps),
             QueryMsg::GetEscrowState {} => Self::get_escrow_state(deps),
-            QueryMsg::GetDisputeState {} => Self::get_dispute_state(deps),
+            QueryMsg::GetDisputeState { start_after, limit } => Self::get_dispute_state(deps, start_after, limit),
+            QueryMsg::GetDispute { member, round } => Self::get_dispute(deps, member, round),
             QueryMsg::GetMemberCount {} => Self::get_member_count(deps),
             QueryMsg::GetTotalContributions {} => Self::get_total_contributions(deps),
             QueryMsg::GetTotalDistributions {} => Self::get_total_distributions(deps),
@@ -61,15 +96,29 @@ impl QueryHandler {
         let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
         let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
         let response = ConfigResponse {
-            admin: config.admin.to_string(),
-            token_denom: config.token_denom,
+            admin: Self::admin(deps)?,
+            token_denom: match &config.denom {
+                Denom::Native(denom) => denom.clone(),
+                Denom::Cw20(token) => token.to_string(),
+            },
+            denom: config.denom,
             contribution_amount: config.contribution_amount.to_string(),
             round_frequency: config.round_frequency,
             beneficiaries: config.beneficiaries.iter().map(|addr| addr.to_string()).collect(),
+            payout_order: config.payout_order.clone(),
             late_penalty: config.late_penalty.to_string(),
             protocol_fees: config.protocol_fees.to_string(),
-            arbitrator: config.arbitrator.to_string(),
+            arbitrator: config.arbitrators.first().map(|addr| addr.to_string()).unwrap_or_default(),
+            arbitrators: config.arbitrators.iter().map(|addr| addr.to_string()).collect(),
+            arbitration_quorum: config.arbitration_quorum,
             time_guards: config.time_guards,
+            max_members: config.max_members,
+            refund_overpayment: config.refund_overpayment,
+            scale_late_penalty: config.scale_late_penalty,
+            auction: config.auction,
+            sealed_bids: config.sealed_bids,
+            bid_bond: config.bid_bond.to_string(),
+            fee_recipient: config.fee_recipient.to_string(),
             is_active: state.is_active,
             is_paused: state.is_paused,
             is_finished: state.is_finished,
@@ -78,63 +127,193 @@ impl QueryHandler {
     }
 
     pub fn get_admin(deps: Deps) -> StdResult<Binary> {
-        let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        to_binary(&config.admin.to_string())
+        to_binary(&Self::admin(deps)?)
     }
 
-    pub fn get_arbitrator(deps: Deps) -> StdResult<Binary> {
-        let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        to_binary(&config.arbitrator.to_string())
+    pub fn get_ownership(deps: Deps) -> StdResult<Binary> {
+        to_binary(&cw_ownable::get_ownership(deps.storage)?)
     }
 
-    // Member queries
-    pub fn get_members(deps: Deps) -> StdResult<Binary> {
-        let members_list: StdResult<Vec<MemberResponse>> = members()
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .map(|item| {
-                let (addr, member) = item?;
-                
-                // Calculate actual balance from rounds
-                let mut balance = Uint128::zero();
-                let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-                
-                for round_num in 1..=state.total_rounds {
-                    if let Ok(round) = get_round(deps.storage, round_num) {
-                        // Check if member contributed to this round
-                        if round.deposits.iter().any(|d| d.member == member.address) {
-                            balance += round.balance;
-                        }
-                    }
-                }
-                
-                Ok(MemberResponse {
-                    address: addr.to_string(),
-                    status: member.status.clone(),
-                    balance: balance.to_string(),
-                    penalties: member.penalties.to_string(),
-                    last_contribution: member.last_contribution,
-                    is_late: member.is_late,
-                })
+    pub fn get_join_requests(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+        let start_after = start_after.map(|address| deps.api.addr_validate(&address)).transpose()?;
+        let (page, next) = Self::page(
+            JOIN_REQUESTS.range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let requests = page
+            .into_iter()
+            .map(|(_, request)| JoinRequestResponse {
+                address: request.address.to_string(),
+                deposit: request.deposit.to_string(),
+                timestamp: request.timestamp,
             })
             .collect();
-        
-        to_binary(&members_list?)
+
+        to_binary(&JoinRequestsResponse { requests, next_start_after: next.map(|addr| addr.to_string()) })
     }
 
-    pub fn get_member(deps: Deps, address: String) -> StdResult<Binary> {
+    pub fn get_waitlist(deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Binary> {
+        // Positions continue from the entries before the page
+        let skipped = match start_after {
+            Some(start) => WAITLIST
+                .keys(deps.storage, None, Some(Bound::inclusive(start)), Order::Ascending)
+                .count() as u64,
+            None => 0,
+        };
+        let (page, next) = Self::page(
+            WAITLIST.range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let entries = page
+            .into_iter()
+            .enumerate()
+            .map(|(index, (_, entry))| WaitlistEntryResponse {
+                address: entry.address.to_string(),
+                position: skipped + index as u64 + 1,
+                bond: entry.bond.to_string(),
+                timestamp: entry.timestamp,
+            })
+            .collect();
+
+        to_binary(&WaitlistResponse { entries, next_start_after: next })
+    }
+
+    // Position on the waitlist, None when the address is not waiting
+    pub fn get_waitlist_position(deps: Deps, address: String) -> StdResult<Binary> {
         let validated_addr = deps.api.addr_validate(&address)?;
-        let member = get_member(deps.storage, &validated_addr).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        let response = MemberResponse {
+        to_binary(&waitlist_position(deps.storage, &validated_addr)?)
+    }
+
+    pub fn get_invitations(deps: Deps, start_after: Option<HexBinary>, limit: Option<u32>) -> StdResult<Binary> {
+        let (page, next) = Self::page(
+            INVITATIONS.range(
+                deps.storage,
+                start_after.as_ref().map(|hash| Bound::exclusive(hash.as_slice())),
+                None,
+                Order::Ascending,
+            ),
+            limit,
+        )?;
+        let invitations = page
+            .into_iter()
+            .map(|(_, invitation)| InvitationResponse {
+                code_hash: invitation.code_hash,
+                max_uses: invitation.max_uses,
+                uses: invitation.uses,
+                expires_at: invitation.expires_at,
+            })
+            .collect();
+
+        to_binary(&InvitationsResponse { invitations, next_start_after: next.map(HexBinary::from) })
+    }
+
+    pub fn get_roles(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+        let start_after = start_after.map(|address| deps.api.addr_validate(&address)).transpose()?;
+        let (page, next) = Self::page(
+            ROLES.range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let assignments = page
+            .into_iter()
+            .map(|(address, roles)| RoleAssignment { address: address.to_string(), roles })
+            .collect();
+
+        to_binary(&RolesResponse { assignments, next_start_after: next.map(|addr| addr.to_string()) })
+    }
+
+    // At most `limit` entries, with the key of the last one when more entries follow
+    fn page<K: Clone, T>(
+        items: impl Iterator<Item = StdResult<(K, T)>>,
+        limit: Option<u32>,
+    ) -> StdResult<Page<K, T>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+        let mut page = items.take(limit + 1).collect::<StdResult<Vec<_>>>()?;
+        if page.len() <= limit {
+            return Ok((page, None));
+        }
+        page.truncate(limit);
+        let next = page.last().map(|(key, _)| key.clone());
+        Ok((page, next))
+    }
+
+    // Current contract owner, empty once ownership has been renounced
+    fn admin(deps: Deps) -> StdResult<String> {
+        let ownership = cw_ownable::get_ownership(deps.storage)?;
+        Ok(ownership.owner.map(|owner| owner.to_string()).unwrap_or_default())
+    }
+
+    pub fn get_arbitrator(deps: Deps) -> StdResult<Binary> {
+        let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+        to_binary(&config.arbitrators.first().map(|addr| addr.to_string()).unwrap_or_default())
+    }
+
+    // Member queries
+    pub fn get_members(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+        let (page, next) = Self::page(
+            members().range(deps.storage, start_after.as_deref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        Self::members_response(deps, page, next)
+    }
+
+    pub fn get_members_by_status(
+        deps: Deps,
+        status: MemberStatus,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let (page, next) = Self::page(
+            members().idx.status.prefix(status_key(&status)).range(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            ),
+            limit,
+        )?;
+        Self::members_response(deps, page, next)
+    }
+
+    fn members_response(deps: Deps, page: Vec<(String, Member)>, next: Option<String>) -> StdResult<Binary> {
+        let rotation = ROTATION.may_load(deps.storage)?.unwrap_or_default();
+        let members_list = page
+            .into_iter()
+            .map(|(_, member)| Self::member_summary(deps, &rotation, member))
+            .collect::<StdResult<Vec<_>>>()?;
+        
+        to_binary(&MembersResponse { members: members_list, next_start_after: next })
+    }
+
+    // Member entry, with the running total of their contributions as balance
+    fn member_summary(deps: Deps, rotation: &[Addr], member: Member) -> StdResult<MemberResponse> {
+        let balance = MEMBER_CONTRIBUTIONS.may_load(deps.storage, &member.address)?.unwrap_or_default();
+
+        Ok(MemberResponse {
             address: member.address.to_string(),
             status: member.status.clone(),
-            balance: "0".to_string(), // Placeholder - would calculate actual balance
+            balance: balance.to_string(),
+            bond: member.balance.to_string(),
             penalties: member.penalties.to_string(),
             last_contribution: member.last_contribution,
             is_late: member.is_late,
-        };
+            payout_round: Self::payout_round(rotation, &member.address),
+            discount_credits: DISCOUNT_CREDITS.may_load(deps.storage, &member.address)?
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+
+    pub fn get_member(deps: Deps, address: String) -> StdResult<Binary> {
+        let validated_addr = deps.api.addr_validate(&address)?;
+        let member = get_member(deps.storage, &validated_addr).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let rotation = ROTATION.may_load(deps.storage)?.unwrap_or_default();
         
-        to_binary(&response)
+        to_binary(&Self::member_summary(deps, &rotation, member)?)
+    }
+
+    // 1-based position of a member in the payout rotation
+    fn payout_round(rotation: &[Addr], member: &Addr) -> Option<u64> {
+        rotation.iter().position(|addr| addr == member).map(|index| index as u64 + 1)
     }
 
     pub fn get_member_status(deps: Deps, address: String) -> StdResult<Binary> {
@@ -147,19 +326,8 @@ impl QueryHandler {
         let validated_addr = deps.api.addr_validate(&address)?;
         let member = get_member(deps.storage, &validated_addr).map_err(|e| StdError::generic_err(e.to_string()))?;
         
-        // Calculate actual balance from rounds
-        let mut balance = Uint128::zero();
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        for round_num in 1..=state.total_rounds {
-            if let Ok(round) = get_round(deps.storage, round_num) {
-                // Check if member contributed to this round
-                if round.deposits.iter().any(|d| d.member == member.address) {
-                    balance += round.balance;
-                }
-            }
-        }
-        
+        let balance = MEMBER_CONTRIBUTIONS.may_load(deps.storage, &member.address)?.unwrap_or_default();
+
         to_binary(&balance.to_string())
     }
 
@@ -172,17 +340,59 @@ impl QueryHandler {
     // Round queries
     pub fn get_current_round(deps: Deps) -> StdResult<Binary> {
         let round = get_current_round(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        to_binary(&round)
+        to_binary(&Self::round_response(deps, round)?)
     }
 
     pub fn get_round_info(deps: Deps, round: u64) -> StdResult<Binary> {
         let round_data = get_round(deps.storage, round).map_err(|e| StdError::generic_err(e.to_string()))?;
-        to_binary(&round_data)
+        to_binary(&Self::round_response(deps, round_data)?)
     }
 
-    pub fn get_round_deposits(deps: Deps, round: u64) -> StdResult<Binary> {
-        let round_data = get_round(deps.storage, round).map_err(|e| StdError::generic_err(e.to_string()))?;
-        to_binary(&round_data.deposits)
+    fn round_response(deps: Deps, round: Round) -> StdResult<RoundResponse> {
+        let deposits = get_round_deposits(deps.storage, round.round_number)?
+            .into_iter()
+            .map(Self::deposit_response)
+            .collect();
+
+        Ok(RoundResponse {
+            round_number: round.round_number,
+            state: round.state,
+            balance: round.balance.to_string(),
+            beneficiary: round.beneficiary.to_string(),
+            deadline: round.deadline,
+            deposits,
+            is_distributed: round.is_distributed,
+            distribution_time: round.distribution_time,
+        })
+    }
+
+    pub fn get_round_deposits(
+        deps: Deps,
+        round: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        // Unknown rounds are an error, as before deposits moved out of the round
+        get_round(deps.storage, round).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let start_after = start_after.map(|address| deps.api.addr_validate(&address)).transpose()?;
+        let (page, next) = Self::page(
+            DEPOSITS
+                .prefix(round)
+                .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let deposits = page.into_iter().map(|(_, deposit)| Self::deposit_response(deposit)).collect();
+
+        to_binary(&RoundDepositsResponse { round, deposits, next_start_after: next.map(|addr| addr.to_string()) })
+    }
+
+    fn deposit_response(deposit: Deposit) -> DepositResponse {
+        DepositResponse {
+            member: deposit.member.to_string(),
+            amount: deposit.amount.to_string(),
+            timestamp: deposit.timestamp,
+            is_late: deposit.is_late,
+        }
     }
 
     pub fn get_round_state(deps: Deps, round: u64) -> StdResult<Binary> {
@@ -190,18 +400,56 @@ impl QueryHandler {
         to_binary(&round_data.state)
     }
 
+    pub fn get_bids(deps: Deps, round: u64, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+        let start_after = start_after.map(|address| deps.api.addr_validate(&address)).transpose()?;
+        let (page, next) = Self::page(
+            BIDS.prefix(round)
+                .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let bids = page
+            .into_iter()
+            .map(|(member, bid)| BidResponse {
+                member: member.to_string(),
+                discount: bid.discount.to_string(),
+                timestamp: bid.timestamp,
+            })
+            .collect();
+
+        to_binary(&BidsResponse { round, bids, next_start_after: next.map(|addr| addr.to_string()) })
+    }
+
+    pub fn get_bid_commitments(
+        deps: Deps,
+        round: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let start_after = start_after.map(|address| deps.api.addr_validate(&address)).transpose()?;
+        let (page, next) = Self::page(
+            BID_COMMITMENTS
+                .prefix(round)
+                .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let commitments = page
+            .into_iter()
+            .map(|(member, commitment)| BidCommitmentResponse {
+                member: member.to_string(),
+                hash: commitment.hash,
+                bond: commitment.bond.to_string(),
+                timestamp: commitment.timestamp,
+                revealed: commitment.revealed,
+            })
+            .collect();
+
+        to_binary(&BidCommitmentsResponse { round, commitments, next_start_after: next.map(|addr| addr.to_string()) })
+    }
+
     pub fn get_tontine_balance(deps: Deps) -> StdResult<Binary> {
-        // Calculate total balance from all rounds
-        let mut total_balance = Uint128::zero();
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        for round_num in 1..=state.total_rounds {
-            if let Ok(round) = get_round(deps.storage, round_num) {
-                total_balance += round.balance;
-            }
-        }
-        
-        to_binary(&total_balance.to_string())
+        // Every contribution stays in its round's balance
+        let totals = get_totals(deps.storage)?;
+        to_binary(&totals.contributions.to_string())
     }
 
     pub fn get_round_balance(deps: Deps, round: u64) -> StdResult<Binary> {
@@ -214,22 +462,43 @@ impl QueryHandler {
         to_binary(&fees.to_string())
     }
 
+    pub fn get_fee_info(deps: Deps) -> StdResult<Binary> {
+        let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let accumulated_fees = get_accumulated_fees(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        let response = FeeInfoResponse {
+            fee_recipient: config.fee_recipient.to_string(),
+            accumulated_fees: accumulated_fees.to_string(),
+            withdrawn_fees: WITHDRAWN_FEES.may_load(deps.storage)?.unwrap_or_default().to_string(),
+            collected_penalties: COLLECTED_PENALTIES.may_load(deps.storage)?.unwrap_or_default().to_string(),
+            withdrawn_penalties: WITHDRAWN_PENALTIES.may_load(deps.storage)?.unwrap_or_default().to_string(),
+        };
+
+        to_binary(&response)
+    }
+
+    pub fn get_refund(deps: Deps, member: String) -> StdResult<Binary> {
+        let member_addr = deps.api.addr_validate(&member)?;
+        let refund = REFUNDS.may_load(deps.storage, &member_addr)?.unwrap_or_default();
+
+        let response = RefundResponse {
+            member,
+            amount: refund.to_string(),
+        };
+
+        to_binary(&response)
+    }
+
+    // Protocol fees taken over the lifetime of the tontine, including collected ones
+    fn lifetime_fees(deps: Deps) -> StdResult<Uint128> {
+        let accumulated_fees = get_accumulated_fees(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let withdrawn_fees = WITHDRAWN_FEES.may_load(deps.storage)?.unwrap_or_default();
+        Ok(accumulated_fees + withdrawn_fees)
+    }
+
     pub fn get_pending_penalties(deps: Deps) -> StdResult<Binary> {
-        let mut pending_penalties = Uint128::zero();
-        
-        // Sum up all unpaid penalties
-        let penalties: StdResult<Vec<_>> = PENALTIES
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .collect();
-        
-        if let Ok(penalties_list) = penalties {
-            for (_, penalty) in penalties_list {
-                if !penalty.is_paid {
-                    pending_penalties += penalty.amount;
-                }
-            }
-        }
-        
+        let totals = get_totals(deps.storage)?;
+        let pending_penalties = totals.penalties_assessed.saturating_sub(totals.penalties_paid);
         to_binary(&pending_penalties.to_string())
     }
 
@@ -240,7 +509,7 @@ impl QueryHandler {
     }
 
     pub fn get_next_beneficiary(deps: Deps) -> StdResult<Binary> {
-        let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let rotation = Self::rotation(deps)?;
         let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
         
         if state.current_round >= state.total_rounds {
@@ -248,8 +517,8 @@ impl QueryHandler {
         }
         
         let next_round = state.current_round + 1;
-        if next_round <= config.beneficiaries.len() as u64 {
-            let next_beneficiary = &config.beneficiaries[(next_round - 1) as usize];
+        if next_round <= rotation.len() as u64 {
+            let next_beneficiary = &rotation[(next_round - 1) as usize];
             to_binary(&next_beneficiary.to_string())
         } else {
             to_binary(&"Invalid beneficiary index")
@@ -265,10 +534,7 @@ impl QueryHandler {
     }
 
     pub fn get_beneficiary_schedule(deps: Deps) -> StdResult<Binary> {
-        let config = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        let _state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        let schedule: Vec<(u64, String)> = config.beneficiaries.iter()
+        let schedule: Vec<(u64, String)> = Self::rotation(deps)?.iter()
             .enumerate()
             .map(|(i, addr)| (i as u64 + 1, addr.to_string()))
             .collect();
@@ -276,6 +542,52 @@ impl QueryHandler {
         to_binary(&schedule)
     }
 
+    // Payout rotation, empty until the tontine starts
+    pub fn get_rotation(deps: Deps) -> StdResult<Binary> {
+        let rotation: Vec<String> = ROTATION.may_load(deps.storage)?
+            .unwrap_or_default()
+            .iter()
+            .map(|addr| addr.to_string())
+            .collect();
+        to_binary(&rotation)
+    }
+
+    // Seed commitments and reveals, with the seed once the random order is drawn
+    pub fn get_random_seed(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
+        let start_after = start_after.map(|address| deps.api.addr_validate(&address)).transpose()?;
+        let (page, next) = Self::page(
+            SEED_COMMITMENTS.range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let commitments = page
+            .into_iter()
+            .map(|(member, commitment)| SeedCommitmentResponse {
+                member: member.to_string(),
+                commitment: commitment.commitment,
+                secret: commitment.secret,
+            })
+            .collect();
+
+        let commit_deadline = SEED_COMMIT_DEADLINE.may_load(deps.storage)?;
+        let time_guards = get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?.time_guards;
+
+        to_binary(&RandomSeedResponse {
+            seed: RANDOM_SEED.may_load(deps.storage)?,
+            commitments,
+            next_start_after: next.map(|addr| addr.to_string()),
+            commit_deadline,
+            reveal_deadline: commit_deadline.map(|deadline| deadline.plus_seconds(time_guards)),
+        })
+    }
+
+    // Rotation once started, the configured beneficiaries before
+    fn rotation(deps: Deps) -> StdResult<Vec<Addr>> {
+        match ROTATION.may_load(deps.storage)? {
+            Some(rotation) => Ok(rotation),
+            None => Ok(get_config(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?.beneficiaries),
+        }
+    }
+
     // Time queries
     pub fn get_round_deadline(deps: Deps, round: u64) -> StdResult<Binary> {
         let round_data = get_round(deps.storage, round).map_err(|e| StdError::generic_err(e.to_string()))?;
@@ -293,102 +605,98 @@ impl QueryHandler {
     }
 
     // Historical queries
-    pub fn get_distribution_history(deps: Deps) -> StdResult<Binary> {
-        let mut distributions = Vec::new();
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        for round_num in 1..=state.total_rounds {
-            if let Ok(distribution) = DISTRIBUTIONS.load(deps.storage, round_num) {
-                let distribution_response = DistributionResponse {
-                    round: distribution.round,
-                    beneficiary: distribution.beneficiary.to_string(),
-                    amount: distribution.amount.to_string(),
-                    timestamp: distribution.timestamp,
-                };
-                distributions.push(distribution_response);
-            }
-        }
-        
-        to_binary(&DistributionHistoryResponse { distributions })
-    }
-
-    pub fn get_penalty_history(deps: Deps) -> StdResult<Binary> {
-        let penalties: StdResult<Vec<_>> = PENALTIES
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+    pub fn get_distribution_history(deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Binary> {
+        let (page, next) = Self::page(
+            DISTRIBUTIONS.range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending),
+            limit,
+        )?;
+        let distributions = page
+            .into_iter()
+            .map(|(_, distribution)| DistributionResponse {
+                round: distribution.round,
+                beneficiary: distribution.beneficiary.to_string(),
+                amount: distribution.amount.to_string(),
+                discount: distribution.discount.to_string(),
+                advanced_from: distribution.advanced_from,
+                timestamp: distribution.timestamp,
+            })
             .collect();
         
-        let penalties_list = penalties.map_err(|e| StdError::generic_err(e.to_string()))?;
-        let penalties_data: Vec<PenaltyResponse> = penalties_list.into_iter().map(|(_, penalty)| PenaltyResponse {
+        to_binary(&DistributionHistoryResponse { distributions, next_start_after: next })
+    }
+
+    pub fn get_penalty_history(
+        deps: Deps,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let start_after = start_after
+            .map(|(member, id)| deps.api.addr_validate(&member).map(|member| (member, id)))
+            .transpose()?;
+        let (page, next) = Self::page(
+            PENALTIES.range(
+                deps.storage,
+                start_after.as_ref().map(|(member, id)| Bound::exclusive((member, *id))),
+                None,
+                Order::Ascending,
+            ),
+            limit,
+        )?;
+        let penalties_data: Vec<PenaltyResponse> = page.into_iter().map(|((_, id), penalty)| PenaltyResponse {
+            id,
             member: penalty.member.to_string(),
             amount: penalty.amount.to_string(),
             reason: penalty.reason,
             timestamp: penalty.timestamp,
             is_paid: penalty.is_paid,
+            payment_time: penalty.payment_time,
         }).collect();
         
-        to_binary(&PenaltyHistoryResponse { penalties: penalties_data })
-    }
-
-    pub fn get_deposit_history(deps: Deps) -> StdResult<Binary> {
-        let mut all_deposits = Vec::new();
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        for round_num in 1..=state.total_rounds {
-            if let Ok(round) = get_round(deps.storage, round_num) {
-                for deposit in &round.deposits {
-                    let deposit_response = DepositResponse {
-                        member: deposit.member.to_string(),
-                        amount: deposit.amount.to_string(),
-                        timestamp: deposit.timestamp,
-                        is_late: deposit.is_late,
-                    };
-                    all_deposits.push(deposit_response);
-                }
-            }
-        }
-        
-        to_binary(&DepositHistoryResponse { deposits: all_deposits })
+        to_binary(&PenaltyHistoryResponse {
+            penalties: penalties_data,
+            next_start_after: next.map(|(member, id)| (member.to_string(), id)),
+        })
+    }
+
+    pub fn get_deposit_history(
+        deps: Deps,
+        start_after: Option<(u64, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let start_after = start_after
+            .map(|(round, member)| deps.api.addr_validate(&member).map(|member| (round, member)))
+            .transpose()?;
+        let (page, next) = Self::page(
+            DEPOSITS.range(
+                deps.storage,
+                start_after.as_ref().map(|(round, member)| Bound::exclusive((*round, member))),
+                None,
+                Order::Ascending,
+            ),
+            limit,
+        )?;
+        let deposits = page.into_iter().map(|(_, deposit)| Self::deposit_response(deposit)).collect();
+        
+        to_binary(&DepositHistoryResponse {
+            deposits,
+            next_start_after: next.map(|(round, member)| (round, member.to_string())),
+        })
     }
 
     pub fn get_tontine_state(deps: Deps) -> StdResult<Binary> {
         let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let totals = get_totals(deps.storage)?;
         
-        // Calculate actual member count
-        let member_count = members()
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .count() as u64;
-        
-        // Calculate actual total balance from all rounds
-        let mut total_balance = Uint128::zero();
-        for round_num in 1..=state.total_rounds {
-            if let Ok(round) = get_round(deps.storage, round_num) {
-                total_balance += round.balance;
-            }
-        }
-        
-        // Get accumulated fees
-        let total_fees = get_accumulated_fees(deps.storage)
-            .map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        // Calculate total penalties
-        let mut total_penalties = Uint128::zero();
-        let penalties: StdResult<Vec<_>> = PENALTIES
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .collect();
-        
-        if let Ok(penalties_list) = penalties {
-            for (_, penalty) in penalties_list {
-                total_penalties += penalty.amount;
-            }
-        }
+        // Get lifetime fees
+        let total_fees = Self::lifetime_fees(deps)?;
         
         let response = TontineStateResponse {
             current_round: state.current_round,
             total_rounds: state.total_rounds,
-            total_balance: total_balance.to_string(),
+            total_balance: totals.contributions.to_string(),
             total_fees: total_fees.to_string(),
-            total_penalties: total_penalties.to_string(),
-            member_count,
+            total_penalties: totals.penalties_assessed.to_string(),
+            member_count: totals.member_count,
             is_active: state.is_active,
             is_paused: state.is_paused,
             is_finished: state.is_finished,
@@ -405,134 +713,98 @@ impl QueryHandler {
             locked_amount: escrow.locked_amount.to_string(),
             lock_reason: escrow.lock_reason,
             lock_timestamp: escrow.lock_timestamp,
+            locked_round: escrow.locked_round,
         };
         
         to_binary(&response)
     }
 
-    pub fn get_dispute_state(_deps: Deps) -> StdResult<Binary> {
-        to_binary(&DisputeStateResponse { active_disputes: vec![] }) // Placeholder
+    pub fn get_dispute_state(
+        deps: Deps,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let start_after = start_after
+            .map(|(member, round)| deps.api.addr_validate(&member).map(|member| (member, round)))
+            .transpose()?;
+        // Open and under review disputes, including appeals
+        let (page, next) = Self::page(
+            DISPUTES
+                .range(
+                    deps.storage,
+                    start_after.as_ref().map(|(member, round)| Bound::exclusive((member, *round))),
+                    None,
+                    Order::Ascending,
+                )
+                .filter(|item| item.as_ref().map_or(true, |(_, dispute)| {
+                    matches!(dispute.status, DisputeStatus::Open | DisputeStatus::UnderReview)
+                })),
+            limit,
+        )?;
+        let active_disputes = page.into_iter().map(|(_, dispute)| Self::dispute_response(dispute)).collect();
+
+        to_binary(&DisputeStateResponse {
+            active_disputes,
+            next_start_after: next.map(|(member, round)| (member.to_string(), round)),
+        })
+    }
+
+    pub fn get_dispute(deps: Deps, member: String, round: u64) -> StdResult<Binary> {
+        let validated_addr = deps.api.addr_validate(&member)?;
+        let dispute = get_dispute(deps.storage, &validated_addr, round).map_err(|e| StdError::generic_err(e.to_string()))?;
+        to_binary(&Self::dispute_response(dispute))
+    }
+
+    fn dispute_response(dispute: Dispute) -> DisputeResponse {
+        DisputeResponse {
+            member: dispute.member.to_string(),
+            round: dispute.round,
+            reason: dispute.reason,
+            timestamp: dispute.timestamp,
+            status: dispute.status,
+            resolution: dispute.resolution,
+            resolution_time: dispute.resolution_time,
+            appealed: dispute.appealed,
+        }
     }
 
     // Statistics queries
     pub fn get_member_count(deps: Deps) -> StdResult<Binary> {
-        let member_count = members()
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .count();
-        to_binary(&(member_count as u64))
+        to_binary(&get_totals(deps.storage)?.member_count)
     }
 
     pub fn get_total_contributions(deps: Deps) -> StdResult<Binary> {
-        let mut total = Uint128::zero();
-        
-        // Get tontine state to know how many rounds exist
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        // Sum up all round balances
-        for round_num in 1..=state.total_rounds {
-            if let Ok(round) = get_round(deps.storage, round_num) {
-                total += round.balance;
-            }
-        }
-        
-        to_binary(&total.to_string())
+        to_binary(&get_totals(deps.storage)?.contributions.to_string())
     }
 
     pub fn get_total_distributions(deps: Deps) -> StdResult<Binary> {
-        let mut total = Uint128::zero();
-        
-        // Get tontine state to know how many rounds exist
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        // Sum up all distributions
-        for round_num in 1..=state.total_rounds {
-            if let Ok(distribution) = DISTRIBUTIONS.load(deps.storage, round_num) {
-                total += distribution.amount;
-            }
-        }
-        
-        to_binary(&total.to_string())
+        to_binary(&get_totals(deps.storage)?.distributions.to_string())
     }
 
     pub fn get_total_penalties(deps: Deps) -> StdResult<Binary> {
-        let mut total = Uint128::zero();
-        
-        // Sum up all penalties
-        let penalties: StdResult<Vec<_>> = PENALTIES
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .collect();
-        
-        if let Ok(penalties_list) = penalties {
-            for (_, penalty) in penalties_list {
-                total += penalty.amount;
-            }
-        }
-        
-        to_binary(&total.to_string())
+        to_binary(&get_totals(deps.storage)?.penalties_assessed.to_string())
     }
 
     pub fn get_total_fees(deps: Deps) -> StdResult<Binary> {
-        let fees = get_accumulated_fees(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
+        let fees = Self::lifetime_fees(deps)?;
         to_binary(&fees.to_string())
     }
 
     pub fn get_statistics(deps: Deps) -> StdResult<Binary> {
-        let member_count = members()
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .count() as u64;
-        
-        let mut total_contributions = Uint128::zero();
-        let mut total_distributions = Uint128::zero();
-        let mut active_rounds = 0u64;
-        let mut completed_rounds = 0u64;
-        
-        // Get tontine state
-        let state = get_tontine_state(deps.storage).map_err(|e| StdError::generic_err(e.to_string()))?;
-        
-        // Calculate round statistics
-        for round_num in 1..=state.total_rounds {
-            if let Ok(round) = get_round(deps.storage, round_num) {
-                total_contributions += round.balance;
-                
-                match round.state {
-                    RoundState::Active => active_rounds += 1,
-                    RoundState::Distributed => completed_rounds += 1,
-                    _ => {}
-                }
-            }
-        }
-        
-        // Calculate total distributions
-        for round_num in 1..=state.total_rounds {
-            if let Ok(distribution) = DISTRIBUTIONS.load(deps.storage, round_num) {
-                total_distributions += distribution.amount;
-            }
-        }
-        
-        // Calculate total penalties
-        let mut total_penalties = Uint128::zero();
-        let penalties: StdResult<Vec<_>> = PENALTIES
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .collect();
-        
-        if let Ok(penalties_list) = penalties {
-            for (_, penalty) in penalties_list {
-                total_penalties += penalty.amount;
-            }
-        }
+        let totals = get_totals(deps.storage)?;
         
-        // Get accumulated fees
-        let total_fees = get_accumulated_fees(deps.storage)
-            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        // Get lifetime fees
+        let total_fees = Self::lifetime_fees(deps)?;
         
         let response = StatisticsResponse {
-            member_count,
-            total_contributions: total_contributions.to_string(),
-            total_distributions: total_distributions.to_string(),
-            total_penalties: total_penalties.to_string(),
+            member_count: totals.member_count,
+            total_contributions: totals.contributions.to_string(),
+            total_distributions: totals.distributions.to_string(),
+            total_penalties: totals.penalties_assessed.to_string(),
+            total_penalties_paid: totals.penalties_paid.to_string(),
             total_fees: total_fees.to_string(),
-            active_rounds,
-            completed_rounds,
+            active_rounds: totals.active_rounds,
+            completed_rounds: totals.completed_rounds,
         };
         
         to_binary(&response)
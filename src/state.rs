@@ -1,23 +1,31 @@
 use cosmwasm_std::{
-    Addr, Storage, Timestamp, Uint128
+    Addr, HexBinary, Order, StdResult, Storage, Timestamp, Uint128
 };
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use serde::{Deserialize, Serialize};
 use crate::error::ContractError;
-use crate::msg::{MemberStatus, RoundState, DisputeStatus};
+use crate::msg::{Denom, EscrowRelease, MemberStatus, PayoutOrder, Role, RoundState, DisputeStatus};
 
-// Configuration - immutable after instantiation
+// Configuration - set at instantiation
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Config {
-    pub admin: Addr,
-    pub token_denom: String,
+    pub denom: Denom,
     pub contribution_amount: Uint128,
     pub round_frequency: u64, // in seconds
     pub beneficiaries: Vec<Addr>,
+    pub payout_order: PayoutOrder,
     pub late_penalty: Uint128,
     pub protocol_fees: Uint128,
-    pub arbitrator: Addr,
+    pub arbitrators: Vec<Addr>,
+    pub arbitration_quorum: u64,
     pub time_guards: u64, // in seconds
+    pub max_members: Option<u64>,
+    pub refund_overpayment: bool,
+    pub scale_late_penalty: bool,
+    pub auction: bool,
+    pub sealed_bids: bool,
+    pub bid_bond: Uint128,
+    pub fee_recipient: Addr,
 }
 
 // Tontine state - mutable
@@ -32,6 +40,18 @@ pub struct TontineState {
     pub last_round_time: Option<Timestamp>,
 }
 
+// Running totals, kept up to date by the execute handlers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Totals {
+    pub contributions: Uint128,
+    pub distributions: Uint128,
+    pub penalties_assessed: Uint128,
+    pub penalties_paid: Uint128,
+    pub member_count: u64,
+    pub active_rounds: u64,
+    pub completed_rounds: u64,
+}
+
 // Member information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Member {
@@ -44,6 +64,38 @@ pub struct Member {
     pub registration_time: Timestamp,
 }
 
+// Invitation to join, identified by the SHA-256 hash of its code
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Invitation {
+    pub code_hash: HexBinary,
+    pub max_uses: u32,
+    pub uses: u32,
+    pub expires_at: Option<Timestamp>,
+}
+
+// Member's commitment to a secret for the random payout order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SeedCommitment {
+    pub commitment: HexBinary,
+    pub secret: Option<String>,
+}
+
+// Address waiting for a free membership slot
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WaitlistEntry {
+    pub address: Addr,
+    pub bond: Uint128,
+    pub timestamp: Timestamp,
+}
+
+// Pending request to join the tontine
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct JoinRequest {
+    pub address: Addr,
+    pub deposit: Uint128,
+    pub timestamp: Timestamp,
+}
+
 // Round information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Round {
@@ -52,7 +104,8 @@ pub struct Round {
     pub balance: Uint128,
     pub beneficiary: Addr,
     pub deadline: Timestamp,
-    pub deposits: Vec<Deposit>,
+    // Deposits are stored in DEPOSITS, balance is their running total
+    pub deposit_count: u64,
     pub is_distributed: bool,
     pub distribution_time: Option<Timestamp>,
 }
@@ -66,6 +119,22 @@ pub struct Deposit {
     pub is_late: bool,
 }
 
+// Discount a member accepts to receive the round's pot
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Bid {
+    pub discount: Uint128,
+    pub timestamp: Timestamp,
+}
+
+// Sealed bid, the hash of "{discount}:{salt}" backed by a bond until revealed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BidCommitment {
+    pub hash: HexBinary,
+    pub bond: Uint128,
+    pub timestamp: Timestamp,
+    pub revealed: bool,
+}
+
 // Penalty information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Penalty {
@@ -83,6 +152,10 @@ pub struct Distribution {
     pub round: u64,
     pub beneficiary: Addr,
     pub amount: Uint128,
+    // Share of the pot given up by the beneficiary to the other contributors
+    pub discount: Uint128,
+    // Scheduled round of a beneficiary paid early through an advance payment
+    pub advanced_from: Option<u64>,
     pub timestamp: Timestamp,
 }
 
@@ -90,11 +163,21 @@ pub struct Distribution {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Dispute {
     pub member: Addr,
+    pub round: u64,
     pub reason: String,
     pub timestamp: Timestamp,
     pub status: DisputeStatus,
     pub resolution: Option<String>,
     pub resolution_time: Option<Timestamp>,
+    pub appealed: bool,
+}
+
+// Arbitrator vote on a dispute
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArbitrationVote {
+    pub decision: String,
+    pub release: EscrowRelease,
+    pub timestamp: Timestamp,
 }
 
 // Escrow state
@@ -104,34 +187,111 @@ pub struct EscrowState {
     pub locked_amount: Uint128,
     pub lock_reason: String,
     pub lock_timestamp: Option<Timestamp>,
+    pub locked_round: Option<u64>,
 }
 
 // Storage keys
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const TONTINE_STATE: Item<TontineState> = Item::new("tontine_state");
 pub const ESCROW_STATE: Item<EscrowState> = Item::new("escrow_state");
+pub const TOTALS: Item<Totals> = Item::new("totals");
+// Beneficiary of each round in order, built when the tontine starts
+pub const ROTATION: Item<Vec<Addr>> = Item::new("rotation");
 
 // Member storage with indexes
+pub struct MemberIndexes<'a> {
+    pub status: MultiIndex<'a, u8, Member, &'a str>,
+    pub registration_time: MultiIndex<'a, u64, Member, &'a str>,
+}
 
+impl<'a> IndexList<Member> for MemberIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Member>> + '_> {
+        let indexes: Vec<&dyn Index<Member>> = vec![&self.status, &self.registration_time];
+        Box::new(indexes.into_iter())
+    }
+}
+
+pub fn members<'a>() -> IndexedMap<'a, &'a str, Member, MemberIndexes<'a>> {
+    let indexes = MemberIndexes {
+        status: MultiIndex::new(|_, member| status_key(&member.status), "members", "members__status"),
+        registration_time: MultiIndex::new(
+            |_, member| member.registration_time.nanos(),
+            "members",
+            "members__registration_time",
+        ),
+    };
+    IndexedMap::new("members", indexes)
+}
 
-pub fn members<'a>() -> Map<'a, &'a str, Member> {
-    Map::new("members")
+// Key of a status in the member status index, stored so it must never change
+pub fn status_key(status: &MemberStatus) -> u8 {
+    match status {
+        MemberStatus::Active => 0,
+        MemberStatus::Inactive => 1,
+        MemberStatus::Suspended => 2,
+        MemberStatus::Replaced => 3,
+        MemberStatus::Excluded => 4,
+    }
 }
 
 // Round storage
 pub const ROUNDS: Map<u64, Round> = Map::new("rounds");
+// Deposits of each round, keyed by (round, member)
+pub const DEPOSITS: Map<(u64, &Addr), Deposit> = Map::new("deposits");
 
 // Penalty storage
 pub const PENALTIES: Map<(&Addr, u64), Penalty> = Map::new("penalties");
+pub const PENALTY_COUNT: Item<u64> = Item::new("penalty_count");
+pub const COLLECTED_PENALTIES: Item<Uint128> = Item::new("collected_penalties");
+// Late penalty id assessed per (round, member)
+pub const LATE_PENALTIES: Map<(u64, &Addr), u64> = Map::new("late_penalties");
 
 // Distribution storage
 pub const DISTRIBUTIONS: Map<u64, Distribution> = Map::new("distributions");
-
-// Dispute storage
+// Discount shares credited to members who financed an early payout
+pub const DISCOUNT_CREDITS: Map<&Addr, Uint128> = Map::new("discount_credits");
+// Running total contributed by each member, less the refunds recorded for them
+pub const MEMBER_CONTRIBUTIONS: Map<&Addr, Uint128> = Map::new("member_contributions");
+// Amount refunded to contributors of each round settled through an escrow release
+pub const ESCROW_REFUNDED: Map<u64, Uint128> = Map::new("escrow_refunded");
+
+// Dispute storage, keyed by (member, round)
 pub const DISPUTES: Map<(&Addr, u64), Dispute> = Map::new("disputes");
+// Arbitrator votes, keyed by (member, round, arbitrator)
+pub const ARBITRATION_VOTES: Map<(&Addr, u64, &Addr), ArbitrationVote> = Map::new("arbitration_votes");
+// Member approvals to replace an arbitrator, keyed by (arbitrator, member)
+pub const ARBITRATOR_REPLACEMENTS: Map<(&Addr, &Addr), Addr> = Map::new("arbitrator_replacements");
+
+// Waitlist in FIFO order, keyed by an increasing id
+pub const WAITLIST: Map<u64, WaitlistEntry> = Map::new("waitlist");
+pub const WAITLIST_COUNT: Item<u64> = Item::new("waitlist_count");
+
+// Invitations keyed by code hash
+pub const INVITATIONS: Map<&[u8], Invitation> = Map::new("invitations");
+
+// Join requests awaiting approval
+pub const JOIN_REQUESTS: Map<&Addr, JoinRequest> = Map::new("join_requests");
+
+// Bids for the pot of each round, keyed by (round, member)
+pub const BIDS: Map<(u64, &Addr), Bid> = Map::new("bids");
+pub const BID_COMMITMENTS: Map<(u64, &Addr), BidCommitment> = Map::new("bid_commitments");
+
+// Commit-reveal of the random payout order, and the seed drawn at start
+pub const SEED_COMMITMENTS: Map<&Addr, SeedCommitment> = Map::new("seed_commitments");
+pub const RANDOM_SEED: Item<HexBinary> = Item::new("random_seed");
+// Set by the first commitment; secrets are revealed within time_guards after it
+pub const SEED_COMMIT_DEADLINE: Item<Timestamp> = Item::new("seed_commit_deadline");
+
+// Roles delegated by the admin
+pub const ROLES: Map<&Addr, Vec<Role>> = Map::new("roles");
+
+// Refunds claimable by members after an early closure
+pub const REFUNDS: Map<&Addr, Uint128> = Map::new("refunds");
 
 // Fee storage
 pub const ACCUMULATED_FEES: Item<Uint128> = Item::new("accumulated_fees");
+pub const WITHDRAWN_FEES: Item<Uint128> = Item::new("withdrawn_fees");
+pub const WITHDRAWN_PENALTIES: Item<Uint128> = Item::new("withdrawn_penalties");
 
 // State management functions
 pub fn initialize_state(storage: &mut dyn Storage) -> Result<(), ContractError> {
@@ -151,14 +311,58 @@ pub fn initialize_state(storage: &mut dyn Storage) -> Result<(), ContractError>
         locked_amount: Uint128::zero(),
         lock_reason: String::new(),
         lock_timestamp: None,
+        locked_round: None,
     };
     ESCROW_STATE.save(storage, &escrow_state)?;
 
     ACCUMULATED_FEES.save(storage, &Uint128::zero())?;
+    WITHDRAWN_FEES.save(storage, &Uint128::zero())?;
+    COLLECTED_PENALTIES.save(storage, &Uint128::zero())?;
+    WITHDRAWN_PENALTIES.save(storage, &Uint128::zero())?;
+    PENALTY_COUNT.save(storage, &0)?;
+    TOTALS.save(storage, &Totals::default())?;
 
     Ok(())
 }
 
+pub fn get_totals(storage: &dyn Storage) -> StdResult<Totals> {
+    TOTALS.load(storage)
+}
+
+pub fn update_totals(storage: &mut dyn Storage, action: impl FnOnce(&mut Totals)) -> StdResult<Totals> {
+    let mut totals = TOTALS.load(storage)?;
+    action(&mut totals);
+    TOTALS.save(storage, &totals)?;
+    Ok(totals)
+}
+
+// Totals rebuilt from rounds, distributions, penalties and members
+pub fn compute_totals(storage: &dyn Storage) -> StdResult<Totals> {
+    let mut totals = Totals::default();
+    for item in ROUNDS.range(storage, None, None, Order::Ascending) {
+        let (_, round) = item?;
+        totals.contributions += round.balance;
+        match round.state {
+            RoundState::Active => totals.active_rounds += 1,
+            RoundState::Distributed => totals.completed_rounds += 1,
+            _ => {}
+        }
+    }
+    for item in DISTRIBUTIONS.range(storage, None, None, Order::Ascending) {
+        let (_, distribution) = item?;
+        totals.distributions += distribution.amount;
+    }
+    for item in PENALTIES.range(storage, None, None, Order::Ascending) {
+        let (_, penalty) = item?;
+        totals.penalties_assessed += penalty.amount;
+        if penalty.is_paid {
+            totals.penalties_paid += penalty.amount;
+        }
+    }
+    totals.member_count = members().keys(storage, None, None, Order::Ascending).count() as u64;
+    Ok(totals)
+}
+
 pub fn get_config(storage: &dyn Storage) -> Result<Config, ContractError> {
     CONFIG.load(storage).map_err(|_| ContractError::InvalidConfiguration { 
         msg: "Configuration not found".to_string() 
@@ -193,6 +397,71 @@ pub fn get_current_round(storage: &dyn Storage) -> Result<Round, ContractError>
     get_round(storage, state.current_round)
 }
 
+// Deposits of a round in the order they were made
+pub fn get_round_deposits(storage: &dyn Storage, round_number: u64) -> StdResult<Vec<Deposit>> {
+    let mut deposits = DEPOSITS
+        .prefix(round_number)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, deposit)| deposit))
+        .collect::<StdResult<Vec<_>>>()?;
+    deposits.sort_by_key(|deposit| deposit.timestamp);
+    Ok(deposits)
+}
+
+pub fn has_deposited(storage: &dyn Storage, round_number: u64, member: &Addr) -> bool {
+    DEPOSITS.has(storage, (round_number, member))
+}
+
+pub fn get_dispute(storage: &dyn Storage, member: &Addr, round: u64) -> Result<Dispute, ContractError> {
+    DISPUTES.load(storage, (member, round)).map_err(|_| ContractError::InvalidDisputeResolution { 
+        msg: format!("No dispute from {} for round {}", member, round) 
+    })
+}
+
+pub fn active_member_count(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(active_members(storage)?.len() as u64)
+}
+
+// Active members ordered by address
+pub fn active_members(storage: &dyn Storage) -> StdResult<Vec<Member>> {
+    members_by_status(storage, &MemberStatus::Active)
+}
+
+// Members with a status ordered by address
+pub fn members_by_status(storage: &dyn Storage, status: &MemberStatus) -> StdResult<Vec<Member>> {
+    members()
+        .idx
+        .status
+        .prefix(status_key(status))
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, member)| member))
+        .collect()
+}
+
+// Active members ordered by registration time, then address
+pub fn active_members_by_registration(storage: &dyn Storage) -> StdResult<Vec<Member>> {
+    members()
+        .idx
+        .registration_time
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, member)| member))
+        .filter(|item| !matches!(item, Ok(member) if member.status != MemberStatus::Active))
+        .collect()
+}
+
+// 1-based position of an address on the waitlist
+pub fn waitlist_position(storage: &dyn Storage, address: &Addr) -> StdResult<Option<u64>> {
+    let mut position = 0;
+    for item in WAITLIST.range(storage, None, None, Order::Ascending) {
+        let (_, entry) = item?;
+        position += 1;
+        if entry.address == *address {
+            return Ok(Some(position));
+        }
+    }
+    Ok(None)
+}
+
 pub fn get_accumulated_fees(storage: &dyn Storage) -> Result<Uint128, ContractError> {
     ACCUMULATED_FEES.load(storage).map_err(|_| ContractError::InvalidFeeManagement { 
         msg: "Accumulated fees not found".to_string() 
@@ -201,6 +470,11 @@ pub fn get_accumulated_fees(storage: &dyn Storage) -> Result<Uint128, ContractEr
 
 // Validation functions
 pub fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if let Denom::Native(denom) = &config.denom {
+        if denom.is_empty() {
+            return Err(ContractError::InvalidTokenDenom { denom: denom.clone() });
+        }
+    }
     if config.contribution_amount == Uint128::zero() {
         return Err(ContractError::InvalidContributionAmount);
     }
@@ -216,6 +490,32 @@ pub fn validate_config(config: &Config) -> Result<(), ContractError> {
     if config.protocol_fees >= config.contribution_amount {
         return Err(ContractError::InvalidProtocolFeesAmount);
     }
+    if config.arbitration_quorum == 0 || config.arbitration_quorum > config.arbitrators.len() as u64 {
+        return Err(ContractError::InvalidArbitration { 
+            msg: format!("Quorum must be between 1 and {}", config.arbitrators.len()) 
+        });
+    }
+    if (1..config.arbitrators.len()).any(|i| config.arbitrators[..i].contains(&config.arbitrators[i])) {
+        return Err(ContractError::InvalidArbitration { 
+            msg: "Duplicate arbitrator".to_string() 
+        });
+    }
+    if config.sealed_bids && !config.auction {
+        return Err(ContractError::InvalidConfiguration { 
+            msg: "Sealed bids require auction mode".to_string() 
+        });
+    }
+    // The reveal window opens time_guards before the deadline and must leave room for commitments
+    if config.sealed_bids && config.time_guards >= config.round_frequency {
+        return Err(ContractError::InvalidConfiguration { 
+            msg: "Sealed bids require time_guards below round_frequency".to_string() 
+        });
+    }
+    if config.max_members == Some(0) {
+        return Err(ContractError::InvalidConfiguration { 
+            msg: "max_members must be at least 1".to_string() 
+        });
+    }
     if config.time_guards == 0 {
         return Err(ContractError::InvalidTimeGuards);
     }
@@ -1,18 +1,74 @@
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info},
-    coins, Addr, Uint128, Timestamp,
+    coin, coins, from_json, to_json_binary, to_json_vec, Addr, Binary, DepsMut, Env, HexBinary, Response, StdResult,
+    Timestamp, Uint128,
 };
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use tontine_contract::{
-    contract::{instantiate, execute, query},
-    msg::{InstantiateMsg, ExecuteMsg, QueryMsg, ConfigResponse, TontineStateResponse},
-    state::{Config, TontineState, Member, MemberStatus},
+    instantiate, execute, query, migrate,
+    msg::{
+        InstantiateMsg, ExecuteMsg, MigrateMsg, QueryMsg, ReceiveMsg, BidCommitmentsResponse, BidsResponse, ConfigResponse, DistributionHistoryResponse, TontineStateResponse,
+        PenaltyHistoryResponse, FeeInfoResponse, RefundResponse, Role, RolesResponse, JoinRequestsResponse, MemberResponse, InvitationsResponse, WaitlistResponse, DisputeStateResponse, DisputeResponse, Denom,
+        DisputeStatus, EscrowRelease, EscrowStateResponse, MembersResponse, MemberStatus, PayoutOrder, RandomSeedResponse, RoundDepositsResponse, RoundResponse, RoundState, StatisticsResponse,
+    },
+    state::{Deposit, Totals, DEPOSITS, MEMBER_CONTRIBUTIONS, TOTALS},
 };
 
 // Mock contract wrapper for testing
 fn mock_contract() -> Box<dyn Contract<cosmwasm_std::Empty>> {
-    let contract = ContractWrapper::new(execute, instantiate, query);
+    let contract = ContractWrapper::new(execute, instantiate, query)
+        .with_migrate(migrate)
+        .with_sudo(storage_sudo);
+    Box::new(contract)
+}
+
+// Test-only sudo entry point giving tests raw access to the contract's storage
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StorageSudoMsg {
+    Set { key: Binary, value: Binary },
+    Remove { key: Binary },
+}
+
+fn storage_sudo(deps: DepsMut, _env: Env, msg: StorageSudoMsg) -> StdResult<Response> {
+    match msg {
+        StorageSudoMsg::Set { key, value } => deps.storage.set(&key, &value),
+        StorageSudoMsg::Remove { key } => deps.storage.remove(&key),
+    }
+    Ok(Response::new())
+}
+
+fn set_raw(app: &mut App, contract_addr: &Addr, key: &[u8], value: &[u8]) {
+    let msg = StorageSudoMsg::Set { key: Binary::from(key), value: Binary::from(value) };
+    app.wasm_sudo(contract_addr.clone(), &msg).unwrap();
+}
+
+fn remove_raw(app: &mut App, contract_addr: &Addr, key: &[u8]) {
+    app.wasm_sudo(contract_addr.clone(), &StorageSudoMsg::Remove { key: Binary::from(key) })
+        .unwrap();
+}
+
+fn load_raw<T: serde::de::DeserializeOwned>(app: &App, contract_addr: &Addr, key: &[u8]) -> T {
+    let value = app.wrap().query_wasm_raw(contract_addr, key).unwrap().unwrap();
+    from_json(value).unwrap()
+}
+
+fn set_contract_version(app: &mut App, contract_addr: &Addr, contract: &str, version: &str) {
+    let info = cw2::ContractVersion { contract: contract.to_string(), version: version.to_string() };
+    set_raw(app, contract_addr, b"contract_info", &to_json_vec(&info).unwrap());
+}
+
+fn cw20_contract() -> Box<dyn Contract<cosmwasm_std::Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
     Box::new(contract)
 }
 
@@ -27,11 +83,22 @@ fn test_contract_instantiation() {
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400, // 1 day in seconds
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600, // 1 hour in seconds
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let result = instantiate(deps.as_mut(), env, info, msg);
@@ -41,18 +108,29 @@ fn test_contract_instantiation() {
 #[test]
 fn test_member_registration() {
     let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -68,7 +146,7 @@ fn test_member_registration() {
 
     // Register a member
     let register_msg = ExecuteMsg::RegisterMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
 
     let result = app.execute_contract(
@@ -81,32 +159,44 @@ fn test_member_registration() {
     assert!(result.is_ok());
 
     // Query members to verify registration
-    let query_msg = QueryMsg::GetMembers {};
-    let response: Vec<Member> = app
+    let query_msg = QueryMsg::GetMembers { start_after: None, limit: None };
+    let response: MembersResponse = app
         .wrap()
         .query_wasm_smart(contract_addr, &query_msg)
         .unwrap();
 
-    assert_eq!(response.len(), 1);
-    assert_eq!(response[0].address, Addr::unchecked("member1"));
-    assert_eq!(response[0].status, MemberStatus::Active);
+    assert_eq!(response.members.len(), 1);
+    assert_eq!(response.members[0].address, MEMBER1);
+    assert_eq!(response.members[0].status, MemberStatus::Active);
+    assert_eq!(response.next_start_after, None);
 }
 
 #[test]
 fn test_tontine_lifecycle() {
     let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -122,7 +212,7 @@ fn test_tontine_lifecycle() {
 
     // Register members
     let register_msg = ExecuteMsg::RegisterMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
 
     app.execute_contract(
@@ -134,7 +224,7 @@ fn test_tontine_lifecycle() {
     .unwrap();
 
     let register_msg2 = ExecuteMsg::RegisterMember {
-        address: "member2".to_string(),
+        address: MEMBER2.to_string(),
     };
 
     app.execute_contract(
@@ -192,19 +282,35 @@ fn test_tontine_lifecycle() {
 
 #[test]
 fn test_contribution_deposit() {
-    let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(MEMBER1), coins(1000, "usaf"))
+            .unwrap();
+    });
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -220,7 +326,7 @@ fn test_contribution_deposit() {
 
     // Register member
     let register_msg = ExecuteMsg::RegisterMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
 
     app.execute_contract(
@@ -244,7 +350,7 @@ fn test_contribution_deposit() {
     // Deposit contribution
     let deposit_msg = ExecuteMsg::DepositContribution {};
     let result = app.execute_contract(
-        Addr::unchecked("member1"),
+        Addr::unchecked(MEMBER1),
         contract_addr.clone(),
         &deposit_msg,
         &coins(1000, "usaf"),
@@ -254,31 +360,40 @@ fn test_contribution_deposit() {
 
     // Query round info to verify deposit
     let query_msg = QueryMsg::GetCurrentRound {};
-    let response = app
+    let response: RoundResponse = app
         .wrap()
         .query_wasm_smart(contract_addr, &query_msg)
         .unwrap();
 
-    // Note: This test would need to be updated based on the actual response structure
-    // For now, we just verify the execution was successful
-    assert!(result.is_ok());
+    assert_eq!(response.deposits.len(), 1);
 }
 
 #[test]
 fn test_unauthorized_operations() {
     let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -294,7 +409,7 @@ fn test_unauthorized_operations() {
 
     // Try to register member as non-admin
     let register_msg = ExecuteMsg::RegisterMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
 
     let result = app.execute_contract(
@@ -321,18 +436,29 @@ fn test_unauthorized_operations() {
 #[test]
 fn test_configuration_queries() {
     let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -385,18 +511,29 @@ fn test_configuration_queries() {
 #[test]
 fn test_member_management() {
     let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -411,7 +548,7 @@ fn test_member_management() {
         .unwrap();
 
     // Register multiple members
-    let members = vec!["member1", "member2", "member3"];
+    let members = vec![MEMBER1, MEMBER2, "addr_safro1member3aaaaaaaaaa"];
     
     for member in &members {
         let register_msg = ExecuteMsg::RegisterMember {
@@ -439,19 +576,19 @@ fn test_member_management() {
 
     // Query specific member
     let query_msg = QueryMsg::GetMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
-    let member: Member = app
+    let member: MemberResponse = app
         .wrap()
         .query_wasm_smart(contract_addr.clone(), &query_msg)
         .unwrap();
 
-    assert_eq!(member.address, Addr::unchecked("member1"));
+    assert_eq!(member.address, MEMBER1);
     assert_eq!(member.status, MemberStatus::Active);
 
     // Remove member
     let remove_msg = ExecuteMsg::RemoveMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
 
     let result = app.execute_contract(
@@ -476,18 +613,29 @@ fn test_member_management() {
 // Helper function to create a test app with contract
 fn create_test_app() -> (App, Addr) {
     let mut app = App::default();
-    let contract_id = app.store_code(Box::new(mock_contract()));
+    let contract_id = app.store_code(mock_contract());
 
     let msg = InstantiateMsg {
         admin: "admin".to_string(),
         token_denom: "usaf".to_string(),
         contribution_amount: "1000".to_string(),
         round_frequency: 86400,
-        beneficiaries: vec!["beneficiary1".to_string(), "beneficiary2".to_string()],
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
         late_penalty: "50".to_string(),
         protocol_fees: "10".to_string(),
         arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
         time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
     };
 
     let contract_addr = app
@@ -509,7 +657,7 @@ fn test_round_operations() {
     let (mut app, contract_addr) = create_test_app();
 
     // Register members
-    let members = vec!["member1", "member2"];
+    let members = vec![MEMBER1, MEMBER2];
     
     for member in &members {
         let register_msg = ExecuteMsg::RegisterMember {
@@ -537,14 +685,13 @@ fn test_round_operations() {
 
     // Query current round
     let query_msg = QueryMsg::GetCurrentRound {};
-    let round = app
+    let round: RoundResponse = app
         .wrap()
         .query_wasm_smart(contract_addr.clone(), &query_msg)
         .unwrap();
 
-    // Note: This test would need to be updated based on the actual response structure
-    // For now, we just verify the query was successful
-    assert!(round.is_ok() || round.is_err()); // Placeholder assertion
+    assert_eq!(round.round_number, 1);
+    assert_eq!(round.state, RoundState::Active);
 }
 
 #[test]
@@ -565,7 +712,7 @@ fn test_error_handling() {
 
     // Try to register duplicate member
     let register_msg = ExecuteMsg::RegisterMember {
-        address: "member1".to_string(),
+        address: MEMBER1.to_string(),
     };
 
     app.execute_contract(
@@ -586,3 +733,2829 @@ fn test_error_handling() {
     // This should fail because member already exists
     assert!(result.is_err());
 }
+
+const MEMBER1: &str = "addr_safro1member1aaaaaaaaaa";
+const MEMBER2: &str = "addr_safro1member2aaaaaaaaaa";
+
+fn funded_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        admin: "admin".to_string(),
+        token_denom: "usaf".to_string(),
+        contribution_amount: "1000".to_string(),
+        round_frequency: 86400,
+        beneficiaries: vec![MEMBER1.to_string(), MEMBER2.to_string()],
+        payout_order: None,
+        late_penalty: "50".to_string(),
+        protocol_fees: "10".to_string(),
+        arbitrator: "arbitrator".to_string(),
+        arbitrators: None,
+        arbitration_quorum: None,
+        time_guards: 3600,
+        max_members: None,
+        refund_overpayment: None,
+        cw20_token: None,
+        scale_late_penalty: None,
+        auction: None,
+        sealed_bids: None,
+        bid_bond: None,
+        fee_recipient: None,
+    }
+}
+
+// Helper function to create a funded test app with two registered members
+fn create_funded_test_app() -> (App, Addr) {
+    create_funded_test_app_with(funded_instantiate_msg())
+}
+
+fn create_funded_test_app_with(msg: InstantiateMsg) -> (App, Addr) {
+    let mut app = App::new(|router, _api, storage| {
+        for member in [MEMBER1, MEMBER2] {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(member),
+                    vec![coin(5000, "usaf"), coin(5000, "uother")],
+                )
+                .unwrap();
+        }
+    });
+    let contract_id = app.store_code(mock_contract());
+
+    let contract_addr = app
+        .instantiate_contract(
+            contract_id,
+            Addr::unchecked("creator"),
+            &msg,
+            &[],
+            "Tontine Contract",
+            None,
+        )
+        .unwrap();
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::RegisterMember { address: member.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+
+    (app, contract_addr)
+}
+
+#[test]
+fn test_multi_round_lifecycle() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    for (round_number, beneficiary) in [(1u64, MEMBER1), (2u64, MEMBER2)] {
+        let round: RoundResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetCurrentRound {})
+            .unwrap();
+        assert_eq!(round.round_number, round_number);
+        assert_eq!(round.state, RoundState::Active);
+        assert_eq!(round.beneficiary, beneficiary);
+
+        for member in [MEMBER1, MEMBER2] {
+            app.execute_contract(
+                Addr::unchecked(member),
+                contract_addr.clone(),
+                &ExecuteMsg::DepositContribution {},
+                &coins(1000, "usaf"),
+            )
+            .unwrap();
+        }
+
+        // Distribution is only possible once the deadline has passed
+        let result = app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::DistributeToBeneficiary {},
+            &[],
+        );
+        assert!(result.is_err());
+
+        app.update_block(|block| block.time = block.time.plus_seconds(86401));
+
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::DistributeToBeneficiary {},
+            &[],
+        )
+        .unwrap();
+
+        let distributed: RoundState = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRoundState { round: round_number })
+            .unwrap();
+        assert_eq!(distributed, RoundState::Distributed);
+    }
+
+    // Each member paid 2 x 1000 and received 2000 minus 2 x 10 fees once
+    for member in [MEMBER1, MEMBER2] {
+        let balance = app.wrap().query_balance(member, "usaf").unwrap();
+        assert_eq!(balance.amount, Uint128::new(4980));
+    }
+
+    let state: TontineStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetTontineState {})
+        .unwrap();
+    assert_eq!(state.current_round, 2);
+    assert_eq!(state.total_rounds, 2);
+    assert!(!state.is_active);
+    assert!(state.is_finished);
+
+    // No further rounds can be distributed or funded once finished
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    );
+    assert!(result.is_err());
+
+    // Finalizing a completed tontine is a no-op that can be repeated
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::FinalizeTontine {},
+            &[],
+        )
+        .unwrap();
+    }
+    let state: TontineStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetTontineState {})
+        .unwrap();
+    assert!(state.is_finished);
+}
+
+#[test]
+fn test_deposit_requires_exact_funds() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    let invalid_funds = vec![
+        vec![],
+        coins(1000, "uother"),
+        vec![coin(1000, "usaf"), coin(1000, "uother")],
+        coins(999, "usaf"),
+        coins(1001, "usaf"),
+    ];
+
+    for funds in invalid_funds {
+        let result = app.execute_contract(
+            Addr::unchecked(MEMBER1),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &funds,
+        );
+        assert!(result.is_err());
+    }
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    let balance = app.wrap().query_balance(contract_addr, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(1000));
+}
+
+#[test]
+fn test_deposit_refunds_overpayment() {
+    let mut msg = funded_instantiate_msg();
+    msg.refund_overpayment = Some(true);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1500, "usaf"),
+    )
+    .unwrap();
+
+    let member_balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(member_balance.amount, Uint128::new(4000));
+
+    let contract_balance = app.wrap().query_balance(&contract_addr, "usaf").unwrap();
+    assert_eq!(contract_balance.amount, Uint128::new(1000));
+
+    // Underpayment is still rejected
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(500, "usaf"),
+    );
+    assert!(result.is_err());
+}
+
+fn cw20_balance(app: &App, token: &Addr, address: &str) -> Uint128 {
+    let response: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: address.to_string() })
+        .unwrap();
+    response.balance
+}
+
+#[test]
+fn test_cw20_contributions_and_payouts() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let contract_id = app.store_code(mock_contract());
+
+    let token = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked("creator"),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Safro Dollar".to_string(),
+                symbol: "SUSD".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin { address: MEMBER1.to_string(), amount: Uint128::new(5000) },
+                    Cw20Coin { address: MEMBER2.to_string(), amount: Uint128::new(5000) },
+                ],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "Stablecoin",
+            None,
+        )
+        .unwrap();
+
+    let mut msg = funded_instantiate_msg();
+    msg.cw20_token = Some(token.to_string());
+    let contract_addr = app
+        .instantiate_contract(
+            contract_id,
+            Addr::unchecked("creator"),
+            &msg,
+            &[],
+            "Tontine Contract",
+            None,
+        )
+        .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetConfig {})
+        .unwrap();
+    assert_eq!(config.denom, Denom::Cw20(token.clone()));
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::RegisterMember { address: member.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    // Native deposits are rejected for CW20 tontines
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: contract_addr.to_string(),
+                amount: Uint128::new(1000),
+                msg: to_json_binary(&ReceiveMsg::DepositContribution {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    assert_eq!(cw20_balance(&app, &token, contract_addr.as_str()), Uint128::new(2000));
+
+    // Underpaying through the receive hook is rejected
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        token.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: contract_addr.to_string(),
+            amount: Uint128::new(10),
+            msg: to_json_binary(&ReceiveMsg::PayPenalty { member: MEMBER1.to_string() }).unwrap(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(cw20_balance(&app, &token, MEMBER1), Uint128::new(5980));
+    assert_eq!(cw20_balance(&app, &token, contract_addr.as_str()), Uint128::new(20));
+}
+
+#[test]
+fn test_penalty_ledger() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("arbitrator"),
+        contract_addr.clone(),
+        &ExecuteMsg::ApplyPenalty { member: MEMBER1.to_string(), amount: "30".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    // Members cannot be declared late before the deadline
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DeclareLate { member: MEMBER1.to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Nor within the time_guards grace window after it
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DeclareLate { member: MEMBER1.to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DeclareLate { member: MEMBER1.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let penalties: String = app
+        .wrap()
+        .query_wasm_smart(
+            contract_addr.clone(),
+            &QueryMsg::GetMemberPenalties { address: MEMBER1.to_string() },
+        )
+        .unwrap();
+    assert_eq!(penalties, "80");
+
+    // Outstanding penalties block deposits
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    );
+    assert!(result.is_err());
+
+    // Partial payment is rejected
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::PayPenalty { member: MEMBER1.to_string() },
+        &coins(50, "usaf"),
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::PayPenalty { member: MEMBER1.to_string() },
+        &coins(80, "usaf"),
+    )
+    .unwrap();
+
+    let history: PenaltyHistoryResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetPenaltyHistory { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(history.penalties.len(), 2);
+    assert_eq!(history.penalties[0].amount, "30");
+    assert_eq!(history.penalties[1].amount, "50");
+    assert!(history.penalties.iter().all(|penalty| penalty.is_paid && penalty.payment_time.is_some()));
+
+    // Cleared penalties unblock deposits
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr,
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_automatic_late_penalties() {
+    let mut msg = funded_instantiate_msg();
+    msg.scale_late_penalty = Some(true);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    // Two started days after the deadline, past the one hour grace window
+    app.update_block(|block| block.time = block.time.plus_seconds(86400 * 2 + 10));
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    // Closing the round penalises the member who never deposited
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    )
+    .unwrap();
+
+    for member in [MEMBER1, MEMBER2] {
+        let penalties: String = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::GetMemberPenalties { address: member.to_string() },
+            )
+            .unwrap();
+        assert_eq!(penalties, "100");
+    }
+
+    let history: PenaltyHistoryResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetPenaltyHistory { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(history.penalties.len(), 2);
+    assert!(history.penalties.iter().all(|penalty| penalty.reason == "Late for round 1"));
+}
+
+#[test]
+fn test_late_deposit_within_grace_window() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86400 + 1800));
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    let penalties: String = app
+        .wrap()
+        .query_wasm_smart(
+            contract_addr.clone(),
+            &QueryMsg::GetMemberPenalties { address: MEMBER1.to_string() },
+        )
+        .unwrap();
+    assert_eq!(penalties, "0");
+
+    // MEMBER2 is still within the grace window, so the round cannot be distributed
+    // around it nor can it be declared late
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    );
+    assert!(result.is_err());
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DeclareLate { member: MEMBER2.to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Once everyone has deposited the round is paid out without waiting for the window to end
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    )
+    .unwrap();
+
+    let penalties: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetPendingPenalties {})
+        .unwrap();
+    assert_eq!(penalties, "0");
+}
+
+#[test]
+fn test_fee_collection_and_withdrawal() {
+    let mut msg = funded_instantiate_msg();
+    msg.fee_recipient = Some("treasury".to_string());
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &coins(1000, "usaf"),
+        )
+        .unwrap();
+    }
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    )
+    .unwrap();
+
+    // Protocol fees go to the fee recipient, which can also pull them itself
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::WithdrawFees {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("treasury"),
+        contract_addr.clone(),
+        &ExecuteMsg::WithdrawFees {},
+        &[],
+    )
+    .unwrap();
+
+    let treasury_balance = app.wrap().query_balance("treasury", "usaf").unwrap();
+    assert_eq!(treasury_balance.amount, Uint128::new(20));
+
+    // Fees are only paid once
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::WithdrawFees {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Paid penalties are the organiser's commission
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ApplyPenalty { member: MEMBER2.to_string(), amount: "30".to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::PayPenalty { member: MEMBER2.to_string() },
+        &coins(30, "usaf"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::WithdrawPenalties {},
+        &[],
+    )
+    .unwrap();
+
+    let admin_balance = app.wrap().query_balance("admin", "usaf").unwrap();
+    assert_eq!(admin_balance.amount, Uint128::new(30));
+
+    let fee_info: FeeInfoResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetFeeInfo {})
+        .unwrap();
+    assert_eq!(fee_info.fee_recipient, "treasury");
+    assert_eq!(fee_info.accumulated_fees, "0");
+    assert_eq!(fee_info.withdrawn_fees, "20");
+    assert_eq!(fee_info.collected_penalties, "0");
+    assert_eq!(fee_info.withdrawn_penalties, "30");
+
+    let total_fees: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetTotalFees {})
+        .unwrap();
+    assert_eq!(total_fees, "20");
+}
+
+fn query_dispute(app: &App, contract_addr: &Addr, member: &str, round: u64) -> DisputeResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr.clone(),
+            &QueryMsg::GetDispute { member: member.to_string(), round },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_dispute_lifecycle() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    let open_msg = ExecuteMsg::OpenDispute { reason: "Missing payout".to_string(), round: 1 };
+
+    // Only active members can open disputes, once per round
+    let result = app.execute_contract(Addr::unchecked("outsider"), contract_addr.clone(), &open_msg, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ExcludeMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &open_msg, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &open_msg, &[])
+        .unwrap();
+
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &open_msg, &[]);
+    assert!(result.is_err());
+
+    let escrow: EscrowStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetEscrowState {})
+        .unwrap();
+    assert_eq!(escrow.locked_round, Some(1));
+
+    let state: DisputeStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetDisputeState { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(state.active_disputes.len(), 1);
+    assert_eq!(state.active_disputes[0].status, DisputeStatus::Open);
+
+    app.execute_contract(
+        Addr::unchecked("arbitrator"),
+        contract_addr.clone(),
+        &ExecuteMsg::ReviewDispute { member: MEMBER1.to_string(), round: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_dispute(&app, &contract_addr, MEMBER1, 1).status, DisputeStatus::UnderReview);
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ResolveDispute {
+            member: MEMBER1.to_string(),
+            round: 1,
+            resolution: "Payout confirmed".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let dispute = query_dispute(&app, &contract_addr, MEMBER1, 1);
+    assert_eq!(dispute.status, DisputeStatus::Resolved);
+    assert_eq!(dispute.resolution, Some("Payout confirmed".to_string()));
+
+    // The resolution releases the round the dispute held in escrow
+    let escrow: EscrowStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetEscrowState {})
+        .unwrap();
+    assert!(!escrow.is_locked);
+    assert_eq!(escrow.locked_round, None);
+
+    let state: DisputeStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetDisputeState { start_after: None, limit: None })
+        .unwrap();
+    assert!(state.active_disputes.is_empty());
+
+    // The member appeals and only the arbitrator can decide the appeal
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::AppealDispute { round: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let dispute = query_dispute(&app, &contract_addr, MEMBER1, 1);
+    assert_eq!(dispute.status, DisputeStatus::UnderReview);
+    assert!(dispute.appealed);
+
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ResolveDispute {
+            member: MEMBER1.to_string(),
+            round: 1,
+            resolution: "Still confirmed".to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("arbitrator"),
+        contract_addr.clone(),
+        &ExecuteMsg::ArbitrateDispute {
+            member: MEMBER1.to_string(),
+            round: 1,
+            decision: "Appeal rejected".to_string(),
+            release: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let dispute = query_dispute(&app, &contract_addr, MEMBER1, 1);
+    assert_eq!(dispute.status, DisputeStatus::Closed);
+    assert_eq!(dispute.resolution, Some("Appeal rejected".to_string()));
+
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr,
+        &ExecuteMsg::AppealDispute { round: 1 },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_dispute_locks_round_escrow() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::OpenDispute { reason: "Beneficiary is absent".to_string(), round: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Contributions made while locked are added to the escrow
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    let escrow: EscrowStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetEscrowState {})
+        .unwrap();
+    assert!(escrow.is_locked);
+    assert_eq!(escrow.locked_amount, "2000");
+    assert_eq!(escrow.locked_round, Some(1));
+
+    // The tontine cannot be closed while a round is held in escrow
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CloseEarly { reason: "Group dissolved".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = app.execute_contract(
+        Addr::unchecked("arbitrator"),
+        contract_addr.clone(),
+        &ExecuteMsg::ArbitrateDispute {
+            member: MEMBER2.to_string(),
+            round: 1,
+            decision: "Too much".to_string(),
+            release: Some(EscrowRelease::Split { beneficiary_percent: 101 }),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Half goes to the beneficiary, the rest is refunded to contributors
+    app.execute_contract(
+        Addr::unchecked("arbitrator"),
+        contract_addr.clone(),
+        &ExecuteMsg::ArbitrateDispute {
+            member: MEMBER2.to_string(),
+            round: 1,
+            decision: "Split the pot".to_string(),
+            release: Some(EscrowRelease::Split { beneficiary_percent: 50 }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5500));
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(4500));
+
+    let escrow: EscrowStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetEscrowState {})
+        .unwrap();
+    assert!(!escrow.is_locked);
+    assert_eq!(escrow.locked_round, None);
+
+    let settled: RoundState = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRoundState { round: 1 })
+        .unwrap();
+    assert_eq!(settled, RoundState::Distributed);
+
+    // The settled round is closed and the next one is open
+    let round: RoundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetCurrentRound {})
+        .unwrap();
+    assert_eq!(round.round_number, 2);
+    assert_eq!(round.state, RoundState::Active);
+
+    // The settled round is not refunded a second time when the tontine is closed
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CloseEarly { reason: "Group dissolved".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRefund { member: MEMBER1.to_string() })
+        .unwrap();
+    assert_eq!(refund.amount, "1000");
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetRefund { member: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(refund.amount, "0");
+}
+
+#[test]
+fn test_migrate_between_code_ids() {
+    let mut app = App::default();
+    let old_code_id = app.store_code(mock_contract());
+    let new_code_id = app.store_code(mock_contract());
+
+    let contract_addr = app
+        .instantiate_contract(
+            old_code_id,
+            Addr::unchecked("creator"),
+            &funded_instantiate_msg(),
+            &[],
+            "Tontine Contract",
+            Some("admin".to_string()),
+        )
+        .unwrap();
+
+    // Rewrite storage as a 0.1.0 contract left it
+    set_contract_version(&mut app, &contract_addr, "crates.io:tontine-contract", "0.1.0");
+    set_raw(
+        &mut app,
+        &contract_addr,
+        b"config",
+        br#"{"admin":"admin","token_denom":"usaf","contribution_amount":"1000","round_frequency":86400,"beneficiaries":["addr_safro1member1aaaaaaaaaa"],"late_penalty":"50","protocol_fees":"10","arbitrator":"arbitrator","time_guards":3600}"#,
+    );
+    set_raw(
+        &mut app,
+        &contract_addr,
+        b"escrow_state",
+        br#"{"is_locked":false,"locked_amount":"0","lock_reason":"","lock_timestamp":null}"#,
+    );
+    let dispute_key = Map::<(&Addr, u64), ()>::new("disputes").key((&Addr::unchecked(MEMBER1), 1));
+    set_raw(
+        &mut app,
+        &contract_addr,
+        &dispute_key,
+        br#"{"member":"addr_safro1member1aaaaaaaaaa","reason":"Late payout","timestamp":"0","status":"open","resolution":null,"resolution_time":null}"#,
+    );
+
+    app.migrate_contract(Addr::unchecked("admin"), contract_addr.clone(), &MigrateMsg {}, new_code_id)
+        .unwrap();
+
+    // Disputes now record their round
+    let dispute = query_dispute(&app, &contract_addr, MEMBER1, 1);
+    assert_eq!(dispute.round, 1);
+    assert!(!dispute.appealed);
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetConfig {})
+        .unwrap();
+    assert_eq!(config.denom, Denom::Native("usaf".to_string()));
+    assert_eq!(config.fee_recipient, "admin");
+    assert!(!config.refund_overpayment);
+
+    let version = cw2::query_contract_info(&app.wrap(), contract_addr.to_string()).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+    // Migrating again to the same version is a no-op
+    app.migrate_contract(Addr::unchecked("admin"), contract_addr.clone(), &MigrateMsg {}, old_code_id)
+        .unwrap();
+
+    // Downgrades and other contracts are refused
+    set_contract_version(&mut app, &contract_addr, "crates.io:tontine-contract", "9.0.0");
+    let result = app.migrate_contract(Addr::unchecked("admin"), contract_addr.clone(), &MigrateMsg {}, new_code_id);
+    assert!(result.is_err());
+
+    set_contract_version(&mut app, &contract_addr, "crates.io:cw20-base", "0.1.0");
+    let result = app.migrate_contract(Addr::unchecked("admin"), contract_addr, &MigrateMsg {}, new_code_id);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_close_early_refunds() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &coins(1000, "usaf"),
+        )
+        .unwrap();
+    }
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    )
+    .unwrap();
+
+    // Only MEMBER2 contributes to the second round before the tontine is closed
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    // A dispute on the paid out round does not lock any funds
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::OpenDispute { reason: "Payout was late".to_string(), round: 1 },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ApplyPenalty { member: MEMBER2.to_string(), amount: "100".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CloseEarly { reason: "Group dissolved".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    // Disputes can no longer be arbitrated once the tontine is closed
+    let result = app.execute_contract(
+        Addr::unchecked("arbitrator"),
+        contract_addr.clone(),
+        &ExecuteMsg::ArbitrateDispute {
+            member: MEMBER2.to_string(),
+            round: 1,
+            decision: "Refund the round".to_string(),
+            release: Some(EscrowRelease::Refund),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // A tontine closed early is settled through refunds, not finalized
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::FinalizeTontine {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    // MEMBER1 already received 1980 for 990 contributed net of fees, so MEMBER2 is owed
+    // the 1000 left, minus the 100 penalty settled from it
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRefund { member: MEMBER1.to_string() })
+        .unwrap();
+    assert_eq!(refund.amount, "0");
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRefund { member: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(refund.amount, "900");
+
+    let penalties: String = app
+        .wrap()
+        .query_wasm_smart(
+            contract_addr.clone(),
+            &QueryMsg::GetMemberPenalties { address: MEMBER2.to_string() },
+        )
+        .unwrap();
+    assert_eq!(penalties, "0");
+    let history: PenaltyHistoryResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetPenaltyHistory { start_after: None, limit: None })
+        .unwrap();
+    assert!(history.penalties.iter().all(|penalty| penalty.is_paid));
+
+    // Member balances track contributions less the refunds recorded
+    let members: MembersResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMembers { start_after: None, limit: None })
+        .unwrap();
+    let balances: Vec<(&str, &str)> = members.members.iter()
+        .map(|member| (member.address.as_str(), member.balance.as_str()))
+        .collect();
+    assert_eq!(balances, vec![(MEMBER1, "1000"), (MEMBER2, "1100")]);
+    let balance: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMemberBalance { address: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(balance, "1100");
+
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::ClaimRefund {},
+        &[],
+    );
+    assert!(result.is_err());
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::ClaimRefund {},
+        &[],
+    )
+    .unwrap();
+
+    let balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5980));
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(3900));
+    let balance = app.wrap().query_balance(&contract_addr, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(120));
+
+    // Refunds can only be claimed once
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr,
+        &ExecuteMsg::ClaimRefund {},
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_close_early_refunds_net_of_fees() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    for round in 1..=2 {
+        for member in [MEMBER1, MEMBER2] {
+            app.execute_contract(
+                Addr::unchecked(member),
+                contract_addr.clone(),
+                &ExecuteMsg::DepositContribution {},
+                &coins(1000, "usaf"),
+            )
+            .unwrap();
+        }
+        if round == 1 {
+            app.update_block(|block| block.time = block.time.plus_seconds(86401));
+            app.execute_contract(
+                Addr::unchecked("admin"),
+                contract_addr.clone(),
+                &ExecuteMsg::DistributeToBeneficiary {},
+                &[],
+            )
+            .unwrap();
+        }
+    }
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CloseEarly { reason: "Group dissolved".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    // Each member put 990 net of fees into the first pot, which MEMBER1 received in full,
+    // so the second round's 2000 covers both positions exactly
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRefund { member: MEMBER1.to_string() })
+        .unwrap();
+    assert_eq!(refund.amount, "10");
+    let refund: RefundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetRefund { member: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(refund.amount, "1990");
+}
+
+#[test]
+fn test_ownership_transfer() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdateOwnership(cw_ownable::Action::TransferOwnership {
+            new_owner: "organiser".to_string(),
+            expiry: None,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    // The proposed owner has no rights until the transfer is accepted
+    let result = app.execute_contract(
+        Addr::unchecked("organiser"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    let result = app.execute_contract(
+        Addr::unchecked("outsider"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdateOwnership(cw_ownable::Action::AcceptOwnership),
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("organiser"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdateOwnership(cw_ownable::Action::AcceptOwnership),
+        &[],
+    )
+    .unwrap();
+
+    let ownership: cw_ownable::Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetOwnership {})
+        .unwrap();
+    assert_eq!(ownership.owner, Some(Addr::unchecked("organiser")));
+    assert_eq!(ownership.pending_owner, None);
+
+    let admin: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetAdmin {})
+        .unwrap();
+    assert_eq!(admin, "organiser");
+
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("organiser"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    // Once renounced nobody can act as admin
+    app.execute_contract(
+        Addr::unchecked("organiser"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdateOwnership(cw_ownable::Action::RenounceOwnership),
+        &[],
+    )
+    .unwrap();
+
+    let result = app.execute_contract(
+        Addr::unchecked("organiser"),
+        contract_addr,
+        &ExecuteMsg::PauseTontine {},
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_arbitrator_panel() {
+    let mut msg = funded_instantiate_msg();
+    msg.arbitrators = Some(vec!["arbitrator2".to_string(), "arbitrator3".to_string()]);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::OpenDispute { reason: "Missing payout".to_string(), round: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let vote = |decision: &str| ExecuteMsg::ArbitrateDispute {
+        member: MEMBER1.to_string(),
+        round: 1,
+        decision: decision.to_string(),
+        release: None,
+    };
+
+    // Two of three arbitrators must agree before the dispute is decided
+    app.execute_contract(Addr::unchecked("arbitrator"), contract_addr.clone(), &vote("Upheld"), &[])
+        .unwrap();
+    app.execute_contract(Addr::unchecked("arbitrator2"), contract_addr.clone(), &vote("Rejected"), &[])
+        .unwrap();
+    assert_eq!(query_dispute(&app, &contract_addr, MEMBER1, 1).status, DisputeStatus::Open);
+
+    let result = app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &vote("Upheld"), &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked("arbitrator2"), contract_addr.clone(), &vote("Upheld"), &[])
+        .unwrap();
+    let dispute = query_dispute(&app, &contract_addr, MEMBER1, 1);
+    assert_eq!(dispute.status, DisputeStatus::Closed);
+    assert_eq!(dispute.resolution, Some("Upheld".to_string()));
+
+    // A majority of active members can replace an arbitrator
+    let replace = ExecuteMsg::ReplaceArbitrator {
+        arbitrator: "arbitrator3".to_string(),
+        replacement: "arbitrator4".to_string(),
+    };
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &replace, &[])
+        .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetConfig {})
+        .unwrap();
+    assert!(config.arbitrators.contains(&"arbitrator3".to_string()));
+
+    let result = app.execute_contract(Addr::unchecked("outsider"), contract_addr.clone(), &replace, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &replace, &[])
+        .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetConfig {})
+        .unwrap();
+    assert_eq!(config.arbitrators, vec!["arbitrator", "arbitrator2", "arbitrator4"]);
+    assert_eq!(config.arbitration_quorum, 2);
+
+    // The admin replaces arbitrators directly
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ReplaceArbitrator {
+            arbitrator: "arbitrator2".to_string(),
+            replacement: "arbitrator5".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr,
+        &ExecuteMsg::ReplaceArbitrator {
+            arbitrator: "arbitrator5".to_string(),
+            replacement: "arbitrator4".to_string(),
+        },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_role_delegation() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    for (address, role) in [("registrar", Role::Registrar), ("operator", Role::Operator), ("treasurer", Role::Treasurer)] {
+        // Only the admin grants roles
+        let grant = ExecuteMsg::GrantRole { address: address.to_string(), role };
+        let result = app.execute_contract(Addr::unchecked(address), contract_addr.clone(), &grant, &[]);
+        assert!(result.is_err());
+
+        app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &grant, &[])
+            .unwrap();
+    }
+
+    let roles: RolesResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRoles { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(roles.assignments.len(), 3);
+
+    // Each role only unlocks its own operations
+    let result = app.execute_contract(
+        Addr::unchecked("operator"),
+        contract_addr.clone(),
+        &ExecuteMsg::RegisterMember { address: "addr_safro1member3aaaaaaaaaa".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("registrar"),
+        contract_addr.clone(),
+        &ExecuteMsg::RegisterMember { address: "addr_safro1member3aaaaaaaaaa".to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("registrar"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: "addr_safro1member3aaaaaaaaaa".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &coins(1000, "usaf"),
+        )
+        .unwrap();
+    }
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+
+    let result = app.execute_contract(
+        Addr::unchecked("treasurer"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("operator"),
+        contract_addr.clone(),
+        &ExecuteMsg::DistributeToBeneficiary {},
+        &[],
+    )
+    .unwrap();
+
+    // The treasurer withdraws protocol fees for the fee recipient
+    app.execute_contract(
+        Addr::unchecked("treasurer"),
+        contract_addr.clone(),
+        &ExecuteMsg::WithdrawFees {},
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance("admin", "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(20));
+
+    // Revoked roles no longer grant access
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RevokeRole { address: "operator".to_string(), role: Role::Operator },
+        &[],
+    )
+    .unwrap();
+
+    let roles: RolesResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRoles { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(roles.assignments.len(), 2);
+    assert!(roles.assignments.iter().all(|assignment| assignment.address != "operator"));
+
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr,
+        &ExecuteMsg::RevokeRole { address: "operator".to_string(), role: Role::Operator },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_join_requests() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::RequestToJoin {},
+        &coins(500, "usaf"),
+    )
+    .unwrap();
+
+    // Members and pending applicants cannot request again
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &ExecuteMsg::RequestToJoin {}, &[]);
+    assert!(result.is_err());
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &ExecuteMsg::RequestToJoin {}, &[]);
+    assert!(result.is_err());
+
+    let requests: JoinRequestsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetJoinRequests { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(requests.requests.len(), 1);
+    assert_eq!(requests.requests[0].address, MEMBER2);
+    assert_eq!(requests.requests[0].deposit, "500");
+
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::ApproveJoin { address: MEMBER2.to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Rejecting returns the deposit
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RejectJoin { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000));
+
+    // Approving keeps the deposit as a bond until the tontine is over
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::RequestToJoin {},
+        &coins(500, "usaf"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ApproveJoin { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(member.status, MemberStatus::Active);
+    assert_eq!(member.bond, "500");
+    assert_eq!(member.balance, "0");
+
+    let requests: JoinRequestsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetJoinRequests { start_after: None, limit: None })
+        .unwrap();
+    assert!(requests.requests.is_empty());
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::StartTontine {},
+        &[],
+    )
+    .unwrap();
+
+    // Requests are closed once the tontine has started
+    let result = app.execute_contract(
+        Addr::unchecked("addr_safro1member3aaaaaaaaaa"),
+        contract_addr.clone(),
+        &ExecuteMsg::RequestToJoin {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CloseEarly { reason: "Group dissolved".to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr, &ExecuteMsg::ClaimRefund {}, &[])
+        .unwrap();
+
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000));
+}
+
+#[test]
+fn test_replace_member_returns_bond() {
+    let mut msg = funded_instantiate_msg();
+    msg.payout_order = Some(PayoutOrder::Registration);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+    let member3 = "addr_safro1member3aaaaaaaaaa";
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::RequestToJoin {},
+        &coins(500, "usaf"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ApproveJoin { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    // The bond goes back to the replaced member, the replacement starts without one
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ReplaceMember { old_address: MEMBER2.to_string(), new_address: member3.to_string() },
+        &[],
+    )
+    .unwrap();
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000));
+
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: member3.to_string() })
+        .unwrap();
+    assert_eq!(member.bond, "0");
+
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CloseEarly { reason: "Group dissolved".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    // Nothing is left to claim, so the bond is paid out exactly once
+    for member in [MEMBER2, member3] {
+        let result = app.execute_contract(Addr::unchecked(member), contract_addr.clone(), &ExecuteMsg::ClaimRefund {}, &[]);
+        assert!(result.is_err());
+    }
+    let balance = app.wrap().query_balance(&contract_addr, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::zero());
+}
+
+#[test]
+fn test_invitation_codes() {
+    let (mut app, contract_addr) = create_funded_test_app();
+    let member3 = "addr_safro1member3aaaaaaaaaa";
+    let member4 = "addr_safro1member4aaaaaaaaaa";
+    let code_hash = |code: &str| HexBinary::from(Sha256::digest(code.as_bytes()).to_vec());
+
+    let expires_at = app.block_info().time.plus_seconds(86400);
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CreateInvitation {
+            code_hash: code_hash("family-and-friends"),
+            max_uses: 2,
+            expires_at: Some(expires_at),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let result = app.execute_contract(
+        Addr::unchecked(member3),
+        contract_addr.clone(),
+        &ExecuteMsg::AcceptInvitation { code: "wrong-code".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Existing members cannot use up an invitation
+    let accept = ExecuteMsg::AcceptInvitation { code: "family-and-friends".to_string() };
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &accept, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(member3), contract_addr.clone(), &accept, &[])
+        .unwrap();
+
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: member3.to_string() })
+        .unwrap();
+    assert_eq!(member.status, MemberStatus::Active);
+
+    let invitations: InvitationsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetInvitations { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(invitations.invitations.len(), 1);
+    assert_eq!(invitations.invitations[0].uses, 1);
+
+    // Expired invitations are refused
+    app.update_block(|block| block.time = block.time.plus_seconds(86400));
+    let result = app.execute_contract(Addr::unchecked(member4), contract_addr.clone(), &accept, &[]);
+    assert!(result.is_err());
+
+    // Single-use invitations are removed once used
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CreateInvitation {
+            code_hash: code_hash("one-time"),
+            max_uses: 1,
+            expires_at: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let accept = ExecuteMsg::AcceptInvitation { code: "one-time".to_string() };
+    app.execute_contract(Addr::unchecked(member4), contract_addr.clone(), &accept, &[])
+        .unwrap();
+
+    let invitations: InvitationsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetInvitations { start_after: None, limit: None })
+        .unwrap();
+    assert!(invitations.invitations.iter().all(|invitation| invitation.code_hash != code_hash("one-time")));
+
+    let result = app.execute_contract(Addr::unchecked("addr_safro1member5aaaaaaaaaa"), contract_addr.clone(), &accept, &[]);
+    assert!(result.is_err());
+
+    // Only the admin or registrars manage invitations
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::RevokeInvitation { code_hash: code_hash("family-and-friends") },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Invitations cannot be accepted once the tontine has started
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::CreateInvitation {
+            code_hash: code_hash("latecomers"),
+            max_uses: 1,
+            expires_at: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder { payout_order: PayoutOrder::Registration },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+    let result = app.execute_contract(
+        Addr::unchecked("addr_safro1member5aaaaaaaaaa"),
+        contract_addr,
+        &ExecuteMsg::AcceptInvitation { code: "latecomers".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_waitlist_promotion() {
+    let mut msg = funded_instantiate_msg();
+    msg.max_members = Some(2);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+    let member3 = "addr_safro1member3aaaaaaaaaa";
+    let member4 = "addr_safro1member4aaaaaaaaaa";
+
+    // The tontine is full, so further registrations are waitlisted in order
+    for address in [member3, member4] {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::RegisterMember { address: address.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let count: u64 = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMemberCount {})
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let position: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetWaitlistPosition { address: member4.to_string() })
+        .unwrap();
+    assert_eq!(position, Some(2));
+
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RegisterMember { address: member4.to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Removing a member promotes the head of the waitlist
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: member3.to_string() })
+        .unwrap();
+    assert_eq!(member.status, MemberStatus::Active);
+
+    let waitlist: WaitlistResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetWaitlist { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(waitlist.entries.len(), 1);
+    assert_eq!(waitlist.entries[0].address, member4);
+    assert_eq!(waitlist.entries[0].position, 1);
+
+    // So does excluding one
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ExcludeMember { address: MEMBER1.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: MEMBER1.to_string() })
+        .unwrap();
+    assert_eq!(member.status, MemberStatus::Excluded);
+
+    let position: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetWaitlistPosition { address: member4.to_string() })
+        .unwrap();
+    assert_eq!(position, None);
+}
+
+#[test]
+fn test_leave_waitlist() {
+    let mut msg = funded_instantiate_msg();
+    msg.max_members = Some(2);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+    let member3 = "addr_safro1member3aaaaaaaaaa";
+
+    // MEMBER2 gives up its place to member3, then asks to join again with a bond
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RegisterMember { address: member3.to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::RequestToJoin {},
+        &coins(500, "usaf"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ApproveJoin { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let waitlist: WaitlistResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetWaitlist { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(waitlist.entries.len(), 1);
+    assert_eq!(waitlist.entries[0].address, MEMBER2);
+
+    // Only waitlisted addresses can leave the waitlist
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &ExecuteMsg::LeaveWaitlist {}, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &ExecuteMsg::LeaveWaitlist {}, &[])
+        .unwrap();
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000));
+
+    let waitlist: WaitlistResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetWaitlist { start_after: None, limit: None })
+        .unwrap();
+    assert!(waitlist.entries.is_empty());
+
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr, &ExecuteMsg::LeaveWaitlist {}, &[]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_payout_rotation() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    let rotation: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRotation {})
+        .unwrap();
+    assert!(rotation.is_empty());
+
+    // Only the admin chooses the order
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder { payout_order: PayoutOrder::Registration },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // An order that skips or repeats a member is rejected at start
+    for order in [vec![MEMBER2], vec![MEMBER2, MEMBER2]] {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::UpdatePayoutOrder {
+                payout_order: PayoutOrder::Custom { order: order.iter().map(|addr| addr.to_string()).collect() },
+            },
+            &[],
+        )
+        .unwrap();
+        let result = app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[]);
+        assert!(result.is_err());
+    }
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder {
+            payout_order: PayoutOrder::Custom { order: vec![MEMBER2.to_string(), MEMBER1.to_string()] },
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+
+    let rotation: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRotation {})
+        .unwrap();
+    assert_eq!(rotation, vec![MEMBER2.to_string(), MEMBER1.to_string()]);
+
+    let state: TontineStateResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetTontineState {})
+        .unwrap();
+    assert_eq!(state.total_rounds, 2);
+
+    let beneficiary: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetCurrentBeneficiary {})
+        .unwrap();
+    assert_eq!(beneficiary, MEMBER2);
+
+    // The order is fixed once started
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder { payout_order: PayoutOrder::Fixed },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // So is membership, a late member would never be paid
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr,
+        &ExecuteMsg::RegisterMember { address: "addr_safro1member3aaaaaaaaaa".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_random_payout_order() {
+    let (mut app, contract_addr) = create_funded_test_app();
+    let commit = |secret: &str| ExecuteMsg::CommitSeed {
+        commitment: HexBinary::from(Sha256::digest(secret.as_bytes()).as_slice()),
+    };
+    let reveal = |secret: &str| ExecuteMsg::RevealSeed { secret: secret.to_string() };
+
+    // Commitments would freeze membership, so a non-random tontine refuses them
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &commit("first secret"), &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder { payout_order: PayoutOrder::Random },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &commit("first secret"), &[])
+        .unwrap();
+
+    // The admin cannot steer the draw once commitments exist
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder { payout_order: PayoutOrder::Registration },
+        &[],
+    );
+    assert!(result.is_err());
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RegisterMember { address: "addr_safro1member3aaaaaaaaaa".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: MEMBER2.to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Secrets stay hidden until every member is committed
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &reveal("first secret"), &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &commit("second secret"), &[])
+        .unwrap();
+
+    let result = app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &reveal("wrong secret"), &[]);
+    assert!(result.is_err());
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &reveal("first secret"), &[])
+        .unwrap();
+
+    // Commitments cannot change once revealing has begun
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &commit("other secret"), &[]);
+    assert!(result.is_err());
+
+    // The draw waits for every reveal
+    let result = app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &reveal("second secret"), &[])
+        .unwrap();
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+
+    // The seed can be recomputed from the revealed secrets
+    let seed: RandomSeedResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRandomSeed { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(seed.commitments.len(), 2);
+    let mut hasher = Sha256::new();
+    for (member, secret) in [(MEMBER1, "first secret"), (MEMBER2, "second secret")] {
+        hasher.update(member.as_bytes());
+        hasher.update(Sha256::digest(secret.as_bytes()));
+    }
+    assert_eq!(seed.seed, Some(HexBinary::from(hasher.finalize().as_slice())));
+    assert_eq!(seed.next_start_after, None);
+
+    // Commitments are paged like the other list queries
+    let page: RandomSeedResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRandomSeed { start_after: None, limit: Some(1) })
+        .unwrap();
+    assert_eq!(page.commitments, seed.commitments[..1].to_vec());
+    assert_eq!(page.next_start_after, Some(seed.commitments[0].member.clone()));
+
+    let mut rotation: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRotation {})
+        .unwrap();
+    rotation.sort();
+    assert_eq!(rotation, vec![MEMBER1.to_string(), MEMBER2.to_string()]);
+
+    // Membership is only frozen until the draw
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ExcludeMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetMember { address: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(member.status, MemberStatus::Excluded);
+}
+
+#[test]
+fn test_unrevealed_seed_forfeits() {
+    let (mut app, contract_addr) = create_funded_test_app();
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::UpdatePayoutOrder { payout_order: PayoutOrder::Random },
+        &[],
+    )
+    .unwrap();
+
+    let commit = |secret: &str| ExecuteMsg::CommitSeed {
+        commitment: HexBinary::from(Sha256::digest(secret.as_bytes()).as_slice()),
+    };
+    let reveal = |secret: &str| ExecuteMsg::RevealSeed { secret: secret.to_string() };
+
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &commit("first secret"), &[])
+        .unwrap();
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &commit("second secret"), &[])
+        .unwrap();
+
+    // Commits close time_guards after the first one, reveals time_guards later
+    let seed: RandomSeedResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRandomSeed { start_after: None, limit: None })
+        .unwrap();
+    let commit_deadline = seed.commit_deadline.unwrap();
+    assert_eq!(seed.reveal_deadline, Some(commit_deadline.plus_seconds(3600)));
+
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &reveal("first secret"), &[])
+        .unwrap();
+
+    // MEMBER2 withholds its secret, which only blocks the start until the reveal deadline
+    let result = app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[]);
+    assert!(result.is_err());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(2 * 3600 + 1));
+
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &reveal("second secret"), &[]);
+    assert!(result.is_err());
+
+    let res = app
+        .execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+    let wasm = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm.attributes.iter().any(|a| a.key == "forfeited_seeds" && a.value == "1"));
+
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: MEMBER2.to_string() })
+        .unwrap();
+    assert_eq!(member.status, MemberStatus::Excluded);
+
+    let rotation: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetRotation {})
+        .unwrap();
+    assert_eq!(rotation, vec![MEMBER1.to_string()]);
+}
+
+#[test]
+fn test_auction_rounds() {
+    let mut msg = funded_instantiate_msg();
+    msg.auction = Some(true);
+    let (mut app, contract_addr) = create_funded_test_app_with(msg);
+
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+
+    // The discount cannot swallow the whole pot of 2 * (1000 - 10)
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::PlaceBid { discount: "1980".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::PlaceBid { discount: "100".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    let bids: BidsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetBids { round: 1, start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(bids.bids.len(), 1);
+    assert_eq!(bids.bids[0].member, MEMBER2);
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &coins(1000, "usaf"),
+        )
+        .unwrap();
+    }
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::DistributeToBeneficiary {}, &[])
+        .unwrap();
+
+    // The bidder takes the pot ahead of the scheduled beneficiary, who receives the discount
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 + 1880));
+    let balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 + 100));
+
+    let rotation: Vec<String> = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRotation {})
+        .unwrap();
+    assert_eq!(rotation, vec![MEMBER2.to_string(), MEMBER1.to_string()]);
+
+    let round: RoundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetCurrentRound {})
+        .unwrap();
+    assert_eq!(round.beneficiary, MEMBER1);
+
+    // Members who already received a pot cannot bid again
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr,
+        &ExecuteMsg::PlaceBid { discount: "10".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_sealed_bid_auction() {
+    let mut msg = funded_instantiate_msg();
+    msg.auction = Some(true);
+    msg.sealed_bids = Some(true);
+    msg.bid_bond = Some("50".to_string());
+    let (mut app, contract_addr) = create_funded_test_app_with(msg.clone());
+
+    // The reveal window cannot swallow the whole round
+    let code_id = app.store_code(mock_contract());
+    msg.time_guards = msg.round_frequency;
+    let result = app.instantiate_contract(code_id, Addr::unchecked("creator"), &msg, &[], "Tontine Contract", None);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+
+    let seal = |discount: &str, salt: &str| ExecuteMsg::CommitBid {
+        hash: HexBinary::from(Sha256::digest(format!("{}:{}", discount, salt).as_bytes()).as_slice()),
+    };
+
+    // Open bids are refused and sealed bids need the bond
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::PlaceBid { discount: "100".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &seal("100", "salt"), &[]);
+    assert!(result.is_err());
+
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &seal("100", "salt"), &coins(50, "usaf"))
+        .unwrap();
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &seal("300", "pepper"), &coins(50, "usaf"))
+        .unwrap();
+
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &coins(1000, "usaf"),
+        )
+        .unwrap();
+    }
+
+    let reveal = ExecuteMsg::RevealBid { discount: "100".to_string(), salt: "salt".to_string() };
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &reveal, &[]);
+    assert!(result.is_err());
+
+    // Inside the time_guards window before the deadline bids are revealed, not committed
+    app.update_block(|block| block.time = block.time.plus_seconds(86400 - 60));
+    let result = app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &seal("200", "salt"), &coins(50, "usaf"));
+    assert!(result.is_err());
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::RevealBid { discount: "200".to_string(), salt: "salt".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &reveal, &[])
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(61));
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::DistributeToBeneficiary {}, &[])
+        .unwrap();
+
+    // The revealed bid wins and gets its bond back; the unrevealed bond is forfeited
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 + 1880));
+    let balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 - 50 + 100));
+
+    let fees: FeeInfoResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetFeeInfo {})
+        .unwrap();
+    assert_eq!(fees.collected_penalties, "50");
+
+    let commitments: BidCommitmentsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetBidCommitments { round: 1, start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(commitments.commitments.len(), 2);
+    assert!(commitments.commitments.iter().any(|c| c.member == MEMBER1 && !c.revealed));
+}
+
+#[test]
+fn test_advance_payment() {
+    let (mut app, contract_addr) = create_funded_test_app();
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+
+    let advance = ExecuteMsg::AdvancePayment { beneficiary: MEMBER2.to_string(), discount: "10".to_string() };
+
+    // The advance is financed by the whole round
+    app.execute_contract(Addr::unchecked(MEMBER1), contract_addr.clone(), &ExecuteMsg::DepositContribution {}, &coins(1000, "usaf"))
+        .unwrap();
+    let result = app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &advance, &[]);
+    assert!(result.is_err());
+    app.execute_contract(Addr::unchecked(MEMBER2), contract_addr.clone(), &ExecuteMsg::DepositContribution {}, &coins(1000, "usaf"))
+        .unwrap();
+
+    // Only payouts still to come can be advanced
+    let result = app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::AdvancePayment { beneficiary: MEMBER1.to_string(), discount: "10".to_string() },
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Paid before the deadline, 10% of the 1980 pot goes to the member who financed it
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &advance, &[])
+        .unwrap();
+
+    let balance = app.wrap().query_balance(MEMBER2, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 + 1782));
+    let balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 + 198));
+
+    let history: DistributionHistoryResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetDistributionHistory { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(history.distributions.len(), 1);
+    assert_eq!(history.distributions[0].beneficiary, MEMBER2);
+    assert_eq!(history.distributions[0].amount, "1782");
+    assert_eq!(history.distributions[0].discount, "198");
+    assert_eq!(history.distributions[0].advanced_from, Some(2));
+
+    // The advanced member is skipped in their scheduled round
+    let member: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMember { address: MEMBER1.to_string() })
+        .unwrap();
+    assert_eq!(member.payout_round, Some(2));
+    assert_eq!(member.discount_credits, "198");
+
+    let round: RoundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::GetCurrentRound {})
+        .unwrap();
+    assert_eq!(round.round_number, 2);
+    assert_eq!(round.beneficiary, MEMBER1);
+}
+
+// Round as stored by 0.1.x contracts, with its deposits inline
+#[derive(Serialize, Deserialize)]
+struct LegacyRound {
+    round_number: u64,
+    state: RoundState,
+    balance: Uint128,
+    beneficiary: Addr,
+    deadline: Timestamp,
+    deposits: Vec<Deposit>,
+    is_distributed: bool,
+    distribution_time: Option<Timestamp>,
+}
+
+#[test]
+fn test_migrate_round_deposits() {
+    let (mut app, _) = create_funded_test_app();
+    let code_id = app.store_code(mock_contract());
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("creator"),
+            &funded_instantiate_msg(),
+            &[],
+            "Tontine Contract",
+            Some("admin".to_string()),
+        )
+        .unwrap();
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::RegisterMember { address: member.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+    for member in [MEMBER1, MEMBER2] {
+        app.execute_contract(
+            Addr::unchecked(member),
+            contract_addr.clone(),
+            &ExecuteMsg::DepositContribution {},
+            &coins(1000, "usaf"),
+        )
+        .unwrap();
+    }
+
+    let before: RoundDepositsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRoundDeposits { round: 1, start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(before.deposits.len(), 2);
+    let round: RoundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetCurrentRound {})
+        .unwrap();
+    assert_eq!(round.deposits.len(), 2);
+
+    // Rewrite storage as a 0.1.0 contract left it
+    set_contract_version(&mut app, &contract_addr, "crates.io:tontine-contract", "0.1.0");
+    set_raw(
+        &mut app,
+        &contract_addr,
+        b"config",
+        br#"{"admin":"admin","token_denom":"usaf","contribution_amount":"1000","round_frequency":86400,"beneficiaries":["addr_safro1member1aaaaaaaaaa","addr_safro1member2aaaaaaaaaa"],"late_penalty":"50","protocol_fees":"10","arbitrator":"arbitrator","time_guards":3600}"#,
+    );
+    set_raw(
+        &mut app,
+        &contract_addr,
+        b"escrow_state",
+        br#"{"is_locked":false,"locked_amount":"0","lock_reason":"","lock_timestamp":null}"#,
+    );
+    let deposits: Vec<Deposit> = round
+        .deposits
+        .iter()
+        .map(|deposit| Deposit {
+            member: Addr::unchecked(&deposit.member),
+            amount: deposit.amount.parse().unwrap(),
+            timestamp: deposit.timestamp,
+            is_late: deposit.is_late,
+        })
+        .collect();
+    for deposit in &deposits {
+        remove_raw(&mut app, &contract_addr, &DEPOSITS.key((1, &deposit.member)));
+        remove_raw(&mut app, &contract_addr, &MEMBER_CONTRIBUTIONS.key(&deposit.member));
+    }
+    let legacy = LegacyRound {
+        round_number: 1,
+        state: round.state.clone(),
+        balance: round.balance.parse().unwrap(),
+        beneficiary: Addr::unchecked(&round.beneficiary),
+        deadline: round.deadline,
+        deposits,
+        is_distributed: false,
+        distribution_time: None,
+    };
+    let key = Map::<u64, LegacyRound>::new("rounds").key(1);
+    set_raw(&mut app, &contract_addr, &key, &to_json_vec(&legacy).unwrap());
+
+    app.migrate_contract(Addr::unchecked("admin"), contract_addr.clone(), &MigrateMsg {}, code_id)
+        .unwrap();
+
+    let migrated: RoundDepositsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetRoundDeposits { round: 1, start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(migrated, before);
+    let migrated: RoundResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetCurrentRound {})
+        .unwrap();
+    assert_eq!(migrated, round);
+    let balance: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMemberBalance { address: MEMBER1.to_string() })
+        .unwrap();
+    assert_eq!(balance, "1000");
+
+    // Migrated deposits still block a second contribution and count towards the payout
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    );
+    assert!(result.is_err());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86401));
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::DistributeToBeneficiary {}, &[])
+        .unwrap();
+    let balance = app.wrap().query_balance(MEMBER1, "usaf").unwrap();
+    assert_eq!(balance.amount, Uint128::new(5000 - 1000 + 1980));
+}
+
+#[test]
+fn test_members_by_status() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::ExcludeMember { address: MEMBER2.to_string() },
+        &[],
+    )
+    .unwrap();
+
+    for (status, expected) in [
+        (MemberStatus::Active, vec![MEMBER1]),
+        (MemberStatus::Excluded, vec![MEMBER2]),
+        (MemberStatus::Suspended, vec![]),
+    ] {
+        let response: MembersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::GetMembersByStatus { status, start_after: None, limit: None },
+            )
+            .unwrap();
+        let addresses: Vec<&str> = response.members.iter().map(|member| member.address.as_str()).collect();
+        assert_eq!(addresses, expected);
+    }
+
+    // Removed members leave the index
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        contract_addr.clone(),
+        &ExecuteMsg::RemoveMember { address: MEMBER1.to_string() },
+        &[],
+    )
+    .unwrap();
+    let response: MembersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::GetMembersByStatus { status: MemberStatus::Active, start_after: None, limit: None },
+        )
+        .unwrap();
+    assert!(response.members.is_empty());
+}
+
+#[test]
+fn test_list_query_pagination() {
+    let (mut app, contract_addr) = create_test_app();
+
+    let addresses: Vec<String> = (0..35).map(|index| format!("addr_safro1member{:02}aaaaaaaaa", index)).collect();
+    for address in &addresses {
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            contract_addr.clone(),
+            &ExecuteMsg::RegisterMember { address: address.clone() },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Default page size is 10 and pages chain through next_start_after
+    let mut listed = vec![];
+    let mut start_after = None;
+    loop {
+        let page: MembersResponse = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMembers { start_after, limit: None })
+            .unwrap();
+        assert!(page.members.len() <= 10);
+        listed.extend(page.members.into_iter().map(|member| member.address));
+        start_after = page.next_start_after;
+        if start_after.is_none() {
+            break;
+        }
+    }
+    assert_eq!(listed, addresses);
+
+    // Limits are capped at 30
+    let page: MembersResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetMembers { start_after: None, limit: Some(100) })
+        .unwrap();
+    assert_eq!(page.members.len(), 30);
+    assert_eq!(page.next_start_after, Some(addresses[29].clone()));
+
+    // The last page has no cursor
+    let page: MembersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::GetMembersByStatus {
+                status: MemberStatus::Active,
+                start_after: Some(addresses[29].clone()),
+                limit: Some(5),
+            },
+        )
+        .unwrap();
+    let page_addresses: Vec<String> = page.members.into_iter().map(|member| member.address).collect();
+    assert_eq!(page_addresses, addresses[30..].to_vec());
+    assert_eq!(page.next_start_after, None);
+}
+
+#[test]
+fn test_running_totals() {
+    let (mut app, contract_addr) = create_funded_test_app();
+
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::StartTontine {}, &[])
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::DepositContribution {},
+        &coins(1000, "usaf"),
+    )
+    .unwrap();
+
+    // MEMBER2 misses the round and is fined when it is distributed
+    app.update_block(|block| block.time = block.time.plus_seconds(86400 + 3601));
+    app.execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::DistributeToBeneficiary {}, &[])
+        .unwrap();
+
+    let stats: StatisticsResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetStatistics {})
+        .unwrap();
+    assert_eq!(stats.member_count, 2);
+    assert_eq!(stats.total_contributions, "1000");
+    assert_eq!(stats.total_distributions, "990");
+    assert_eq!(stats.total_penalties, "50");
+    assert_eq!(stats.total_penalties_paid, "0");
+    assert_eq!(stats.total_fees, "10");
+    assert_eq!(stats.active_rounds, 1);
+    assert_eq!(stats.completed_rounds, 1);
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER2),
+        contract_addr.clone(),
+        &ExecuteMsg::PayPenalty { member: MEMBER2.to_string() },
+        &coins(50, "usaf"),
+    )
+    .unwrap();
+    let pending: String = app
+        .wrap()
+        .query_wasm_smart(contract_addr.clone(), &QueryMsg::GetPendingPenalties {})
+        .unwrap();
+    assert_eq!(pending, "0");
+
+    // Only the admin can rebuild the totals
+    let result = app.execute_contract(
+        Addr::unchecked(MEMBER1),
+        contract_addr.clone(),
+        &ExecuteMsg::RecomputeTotals {},
+        &[],
+    );
+    assert!(result.is_err());
+
+    // Totals kept by the handlers match the ones rebuilt from storage
+    let response = app
+        .execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::RecomputeTotals {}, &[])
+        .unwrap();
+    assert!(response.events.iter().any(|event| {
+        event.attributes.iter().any(|attr| attr.key == "drifted" && attr.value == "none")
+    }));
+
+    // Drifted totals are reported and repaired
+    let expected: Totals = load_raw(&app, &contract_addr, TOTALS.as_slice());
+    let drifted = Totals { contributions: Uint128::new(1), member_count: 9, ..expected.clone() };
+    set_raw(&mut app, &contract_addr, TOTALS.as_slice(), &to_json_vec(&drifted).unwrap());
+    let response = app
+        .execute_contract(Addr::unchecked("admin"), contract_addr.clone(), &ExecuteMsg::RecomputeTotals {}, &[])
+        .unwrap();
+    assert!(response.events.iter().any(|event| {
+        event.attributes.iter().any(|attr| attr.key == "drifted" && attr.value == "contributions,member_count")
+    }));
+    let repaired: Totals = load_raw(&app, &contract_addr, TOTALS.as_slice());
+    assert_eq!(repaired, expected);
+}